@@ -0,0 +1,422 @@
+//! Bulls and cows: guess an N-digit code whose digits are all different.
+//!
+//! Every guess is scored with bulls (right digit in the right place) and
+//! cows (right digit in the wrong place) instead of an `Ordering`.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::error::Error;
+use std::fmt;
+
+/// The longest code possible with unique decimal digits.
+pub const MAX_DIGITS: usize = 10;
+
+/// For 4-digit codes, every secret can be found in at most this many
+/// guesses, and some secrets need all of them.
+pub const OPTIMAL_WORST_CASE_4: u32 = 7;
+
+/// The longest code the [`Solver`] searches by minimax. The search costs
+/// the number of codes times the number of candidates per guess, which
+/// is already 900 million for 5 digits.
+pub const MAX_MINIMAX_DIGITS: usize = 4;
+
+/// A code made of distinct decimal digits; leading zeros are allowed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Code {
+    digits: Vec<u8>,
+    // Bit `d` is set when digit `d` appears, and bit `10 * i + d` of
+    // `placed` when it appears at index `i`, which makes scoring cheap.
+    mask: u16,
+    placed: u128,
+}
+
+impl Code {
+    /// Builds a code from digits, checking that they are valid and unique.
+    pub fn new(digits: Vec<u8>) -> Result<Code, CodeError> {
+        let mut mask = 0u16;
+        let mut placed = 0u128;
+        for (index, &digit) in digits.iter().enumerate() {
+            if digit > 9 {
+                return Err(CodeError::OutOfRange(digit));
+            }
+            if mask & (1 << digit) != 0 {
+                return Err(CodeError::RepeatedDigit(digit));
+            }
+            mask |= 1 << digit;
+            placed |= 1 << (10 * index + usize::from(digit));
+        }
+
+        Ok(Code {
+            digits,
+            mask,
+            placed,
+        })
+    }
+
+    /// Parses a guess typed by the player, which must have `len` digits.
+    pub fn parse(input: &str, len: usize) -> Result<Code, CodeError> {
+        let input = input.trim();
+        let found = input.chars().count();
+        if found != len {
+            return Err(CodeError::WrongLength {
+                expected: len,
+                found,
+            });
+        }
+
+        let digits = input
+            .chars()
+            .map(|c| {
+                c.to_digit(10)
+                    .map(|d| d as u8)
+                    .ok_or(CodeError::NotADigit(c))
+            })
+            .collect::<Result<Vec<u8>, CodeError>>()?;
+
+        Code::new(digits)
+    }
+
+    /// Picks a random code of `len` distinct digits.
+    ///
+    /// Panics if `len` is more than [`MAX_DIGITS`].
+    pub fn random<R: Rng + ?Sized>(len: usize, rng: &mut R) -> Code {
+        assert!(len <= MAX_DIGITS, "A code has at most 10 unique digits.");
+
+        let mut digits: Vec<u8> = (0..10).collect();
+        digits.shuffle(rng);
+        digits.truncate(len);
+        Code::new(digits).unwrap()
+    }
+
+    pub fn digits(&self) -> &[u8] {
+        &self.digits
+    }
+
+    pub fn len(&self) -> usize {
+        self.digits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.digits.is_empty()
+    }
+}
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for digit in &self.digits {
+            write!(f, "{}", digit)?;
+        }
+        Ok(())
+    }
+}
+
+/// Why some input is not a valid code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodeError {
+    WrongLength {
+        expected: usize,
+        found: usize,
+    },
+    NotADigit(char),
+    /// A number above 9 given where a digit was expected.
+    OutOfRange(u8),
+    RepeatedDigit(u8),
+}
+
+impl fmt::Display for CodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CodeError::WrongLength { expected, found } => {
+                write!(
+                    f,
+                    "The code has {} digits, but you typed {}.",
+                    expected, found
+                )
+            }
+            CodeError::NotADigit(c) => write!(f, "`{}` is not a digit.", c),
+            CodeError::OutOfRange(n) => write!(f, "{} is not a digit from 0 to 9.", n),
+            CodeError::RepeatedDigit(d) => {
+                write!(
+                    f,
+                    "The digit {} appears more than once; digits must differ.",
+                    d
+                )
+            }
+        }
+    }
+}
+
+impl Error for CodeError {}
+
+/// The feedback for one guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Score {
+    pub bulls: u8,
+    pub cows: u8,
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} bulls, {} cows", self.bulls, self.cows)
+    }
+}
+
+/// Scores `guess` against `secret`; both must have the same length.
+pub fn score(secret: &Code, guess: &Code) -> Score {
+    let bulls = (secret.placed & guess.placed).count_ones() as u8;
+    let common = (secret.mask & guess.mask).count_ones() as u8;
+
+    Score {
+        bulls,
+        cows: common - bulls,
+    }
+}
+
+/// Every code of `len` distinct digits, in increasing order.
+pub fn all_codes(len: usize) -> Vec<Code> {
+    fn extend(prefix: &mut Vec<u8>, len: usize, codes: &mut Vec<Code>) {
+        if prefix.len() == len {
+            codes.push(Code::new(prefix.clone()).unwrap());
+            return;
+        }
+        for digit in 0..10 {
+            if !prefix.contains(&digit) {
+                prefix.push(digit);
+                extend(prefix, len, codes);
+                prefix.pop();
+            }
+        }
+    }
+
+    let mut codes = Vec::new();
+    extend(&mut Vec::new(), len, &mut codes);
+    codes
+}
+
+/// One round of bulls and cows.
+#[derive(Debug, Clone)]
+pub struct CowsGame {
+    secret: Code,
+    history: Vec<(Code, Score)>,
+}
+
+impl CowsGame {
+    pub fn new<R: Rng + ?Sized>(len: usize, rng: &mut R) -> CowsGame {
+        CowsGame::with_secret(Code::random(len, rng))
+    }
+
+    pub fn with_secret(secret: Code) -> CowsGame {
+        CowsGame {
+            secret,
+            history: Vec::new(),
+        }
+    }
+
+    /// Scores a guess. Panics if it has the wrong length.
+    pub fn guess(&mut self, guess: Code) -> Score {
+        assert_eq!(
+            guess.len(),
+            self.secret.len(),
+            "Guess has the wrong length."
+        );
+
+        let score = score(&self.secret, &guess);
+        self.history.push((guess, score));
+        score
+    }
+
+    pub fn secret(&self) -> &Code {
+        &self.secret
+    }
+
+    pub fn len(&self) -> usize {
+        self.secret.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.secret.is_empty()
+    }
+
+    pub fn history(&self) -> &[(Code, Score)] {
+        &self.history
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.history.len() as u32
+    }
+
+    pub fn is_won(&self) -> bool {
+        matches!(self.history.last(), Some((_, score)) if usize::from(score.bulls) == self.len())
+    }
+}
+
+/// Plays bulls and cows by minimax: each guess is the code whose worst
+/// possible answer leaves the fewest candidates.
+///
+/// For 4 digits it never needs more than [`OPTIMAL_WORST_CASE_4`] guesses.
+/// Codes longer than [`MAX_MINIMAX_DIGITS`] would take too long to search,
+/// so for those it just guesses the first code that is still possible.
+#[derive(Debug, Clone)]
+pub struct Solver {
+    // Every code, to pick guesses from; empty when not searching.
+    all: Vec<Code>,
+    candidates: Vec<Code>,
+}
+
+impl Solver {
+    pub fn new(len: usize) -> Solver {
+        let candidates = all_codes(len);
+        let all = if len <= MAX_MINIMAX_DIGITS {
+            candidates.clone()
+        } else {
+            Vec::new()
+        };
+
+        Solver { all, candidates }
+    }
+
+    pub fn candidates(&self) -> &[Code] {
+        &self.candidates
+    }
+
+    pub fn next_guess(&self) -> Code {
+        // Every first guess is as good as any other, so skip the search.
+        let first = self.candidates.len() == self.all.len();
+        if self.all.is_empty() || first || self.candidates.len() <= 2 {
+            return self.candidates[0].clone();
+        }
+
+        let len = self.all[0].len();
+        let mut best: Option<(usize, bool, &Code)> = None;
+        let mut partitions = vec![0usize; (len + 1) * (len + 1)];
+
+        for guess in &self.all {
+            partitions.iter_mut().for_each(|count| *count = 0);
+            for candidate in &self.candidates {
+                let score = score(candidate, guess);
+                partitions[usize::from(score.bulls) * (len + 1) + usize::from(score.cows)] += 1;
+            }
+
+            let worst = *partitions.iter().max().unwrap();
+            let replace = match best {
+                None => true,
+                Some((best_worst, _, _)) if worst < best_worst => true,
+                // Among equally good guesses, prefer one that could win outright.
+                Some((best_worst, false, _)) if worst == best_worst => {
+                    self.candidates.contains(guess)
+                }
+                _ => false,
+            };
+            if replace {
+                best = Some((worst, self.candidates.contains(guess), guess));
+            }
+        }
+
+        best.unwrap().2.clone()
+    }
+
+    /// Drops every candidate that would not have scored `score` on `guess`.
+    pub fn feedback(&mut self, guess: &Code, result: Score) {
+        self.candidates
+            .retain(|candidate| score(candidate, guess) == result);
+    }
+}
+
+/// Lets a [`Solver`] play `game` to the end and returns the guesses used.
+pub fn solve(game: &mut CowsGame) -> u32 {
+    let mut solver = Solver::new(game.len());
+
+    while !game.is_won() {
+        let guess = solver.next_guess();
+        let result = game.guess(guess.clone());
+        solver.feedback(&guess, result);
+    }
+
+    game.attempts()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seeded_rng;
+
+    fn code(s: &str) -> Code {
+        Code::parse(s, s.len()).unwrap()
+    }
+
+    #[test]
+    fn scores_bulls_and_cows() {
+        assert_eq!(
+            score(&code("1234"), &code("1243")),
+            Score { bulls: 2, cows: 2 }
+        );
+        assert_eq!(
+            score(&code("1234"), &code("5678")),
+            Score { bulls: 0, cows: 0 }
+        );
+        assert_eq!(
+            score(&code("0123"), &code("3210")),
+            Score { bulls: 0, cows: 4 }
+        );
+    }
+
+    #[test]
+    fn validates_input() {
+        assert_eq!(
+            Code::parse("123", 4),
+            Err(CodeError::WrongLength {
+                expected: 4,
+                found: 3
+            })
+        );
+        assert_eq!(Code::parse("12a4", 4), Err(CodeError::NotADigit('a')));
+        assert_eq!(Code::parse("1224", 4), Err(CodeError::RepeatedDigit(2)));
+        assert_eq!(Code::new(vec![1, 12]), Err(CodeError::OutOfRange(12)));
+        assert_eq!(Code::parse(" 0123\n", 4).unwrap().to_string(), "0123");
+    }
+
+    #[test]
+    fn random_codes_have_unique_digits() {
+        let mut rng = seeded_rng(3);
+        for len in 1..=MAX_DIGITS {
+            let code = Code::random(len, &mut rng);
+            assert_eq!(Code::new(code.digits().to_vec()), Ok(code.clone()));
+            assert_eq!(code.len(), len);
+        }
+    }
+
+    #[test]
+    fn counts_all_codes() {
+        assert_eq!(all_codes(1).len(), 10);
+        assert_eq!(all_codes(4).len(), 5040);
+    }
+
+    #[test]
+    fn solver_wins_within_the_optimal_bound() {
+        // Every 97th secret; the exhaustive check is the ignored test below.
+        let all = all_codes(4);
+        for secret in all.iter().step_by(97) {
+            let mut game = CowsGame::with_secret(secret.clone());
+            assert!(solve(&mut game) <= OPTIMAL_WORST_CASE_4, "{}", secret);
+        }
+    }
+
+    #[test]
+    #[ignore = "slow in debug builds; run with --release -- --ignored"]
+    fn solver_wins_every_4_digit_game_within_the_optimal_bound() {
+        for secret in all_codes(4) {
+            let mut game = CowsGame::with_secret(secret.clone());
+            assert!(solve(&mut game) <= OPTIMAL_WORST_CASE_4, "{}", secret);
+        }
+    }
+
+    #[test]
+    fn solver_handles_other_lengths() {
+        let mut rng = seeded_rng(8);
+        for len in [1, 2, 3, 6] {
+            let mut game = CowsGame::new(len, &mut rng);
+            solve(&mut game);
+            assert!(game.is_won());
+        }
+    }
+}
@@ -0,0 +1,361 @@
+use crate::i18n::Lang;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// Named presets that pick both the range and the attempt budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn range(self) -> RangeInclusive<i64> {
+        match self {
+            Difficulty::Easy => 1..=10,
+            Difficulty::Normal => 1..=100,
+            Difficulty::Hard => 1..=1_000_000,
+        }
+    }
+
+    pub fn max_attempts(self) -> u32 {
+        match self {
+            Difficulty::Easy => 5,
+            Difficulty::Normal => 10,
+            Difficulty::Hard => 30,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Difficulty, String> {
+        match s.to_ascii_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(format!(
+                "unknown difficulty `{}` (expected easy, normal or hard)",
+                s
+            )),
+        }
+    }
+}
+
+/// Settings for one run of the command-line game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub range: RangeInclusive<i64>,
+    pub max_attempts: Option<u32>,
+    pub difficulty: Option<Difficulty>,
+    pub seed: Option<u64>,
+    pub name: Option<String>,
+    /// Play against the adversarial host from [`crate::evil`].
+    pub evil: bool,
+    /// Read guesses from this file, or from stdin for `-`, without prompts.
+    pub script: Option<String>,
+    /// Print one JSON record per guess instead of text.
+    pub json: bool,
+    /// Say how close each guess was and allow paid hints.
+    pub hints: bool,
+    /// Show the remaining interval and the bits each guess earned.
+    pub info: bool,
+    /// Save the replay here instead of the data directory.
+    pub record: Option<String>,
+    /// The language to talk to the player in; `None` means from `$LANG`.
+    pub lang: Option<Lang>,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            range: 1..=100,
+            max_attempts: None,
+            difficulty: None,
+            seed: None,
+            name: None,
+            evil: false,
+            script: None,
+            json: false,
+            hints: false,
+            info: false,
+            record: None,
+            lang: None,
+        }
+    }
+}
+
+impl Config {
+    /// Builds a config from command-line arguments, without the program name.
+    ///
+    /// `--min` and `--max` override the bounds of a `--difficulty` preset
+    /// but keep its attempt budget; `--max-guesses` overrides the budget.
+    pub fn from_args<I>(args: I) -> Result<Config, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut difficulty: Option<Difficulty> = None;
+        let mut min = None;
+        let mut max = None;
+        let mut seed = None;
+        let mut name = None;
+        let mut evil = false;
+        let mut script = None;
+        let mut json = false;
+        let mut hints = false;
+        let mut info = false;
+        let mut record = None;
+        let mut lang = None;
+        let mut max_attempts = None;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--difficulty" | "-d" => difficulty = Some(value(&arg, args.next())?.parse()?),
+                "--min" => min = Some(number(&arg, args.next())?),
+                "--max" => max = Some(number(&arg, args.next())?),
+                "--seed" => seed = Some(number(&arg, args.next())?),
+                "--evil" => evil = true,
+                "--script" => script = Some(value(&arg, args.next())?),
+                "--json" => json = true,
+                "--hints" => hints = true,
+                "--info" => info = true,
+                "--record" => record = Some(value(&arg, args.next())?),
+                "--lang" => lang = Some(value(&arg, args.next())?.parse()?),
+                "--name" => name = Some(value(&arg, args.next())?),
+                "--max-guesses" => max_attempts = Some(number(&arg, args.next())?),
+                _ => return Err(format!("unexpected argument `{}`", arg)),
+            }
+        }
+
+        let mut config = Config::default();
+        if let Some(difficulty) = difficulty {
+            config.range = difficulty.range();
+            config.max_attempts = Some(difficulty.max_attempts());
+            config.difficulty = Some(difficulty);
+        }
+
+        let min = min.unwrap_or(*config.range.start());
+        let max = max.unwrap_or(*config.range.end());
+        if min > max {
+            return Err(format!(
+                "--min ({}) must not be greater than --max ({})",
+                min, max
+            ));
+        }
+        config.range = min..=max;
+        config.seed = seed;
+        config.name = name;
+        config.evil = evil;
+        config.script = script;
+        config.json = json;
+        config.hints = hints;
+        config.info = info;
+        config.record = record;
+        config.lang = lang;
+        if hints && evil {
+            return Err("--hints cannot be used with --evil".to_string());
+        }
+        if let Some(max_attempts) = max_attempts {
+            if max_attempts == 0 {
+                return Err("--max-guesses must be at least 1".to_string());
+            }
+            config.max_attempts = Some(max_attempts);
+        }
+
+        Ok(config)
+    }
+
+    /// The flags given for options that not every subcommand supports,
+    /// such as `--json`, so a subcommand can refuse the ones it would
+    /// otherwise ignore.
+    pub fn optional_flags(&self) -> Vec<&'static str> {
+        [
+            ("--seed", self.seed.is_some()),
+            ("--name", self.name.is_some()),
+            ("--evil", self.evil),
+            ("--script", self.script.is_some()),
+            ("--json", self.json),
+            ("--hints", self.hints),
+            ("--info", self.info),
+            ("--record", self.record.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(flag, given)| given.then_some(flag))
+        .collect()
+    }
+
+    /// The high-score category: the preset's name, or `custom` when the
+    /// range has been changed by hand, prefixed with `evil-` against the
+    /// adversarial host.
+    pub fn label(&self) -> String {
+        let base = match self.difficulty {
+            Some(difficulty) if difficulty.range() == self.range => difficulty.name(),
+            _ => "custom",
+        };
+
+        if self.evil {
+            format!("evil-{}", base)
+        } else {
+            base.to_string()
+        }
+    }
+}
+
+fn value(flag: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("`{}` needs a value", flag))
+}
+
+fn number<T: FromStr>(flag: &str, arg: Option<String>) -> Result<T, String> {
+    let arg = value(flag, arg)?;
+    arg.parse()
+        .map_err(|_| format!("`{}` expects a whole number, got `{}`", flag, arg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Config, String> {
+        Config::from_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn defaults_to_one_to_one_hundred() {
+        assert_eq!(parse(&[]), Ok(Config::default()));
+    }
+
+    #[test]
+    fn preset_sets_range_and_budget() {
+        let config = parse(&["--difficulty", "hard"]).unwrap();
+
+        assert_eq!(config.range, 1..=1_000_000);
+        assert_eq!(config.max_attempts, Some(30));
+        assert_eq!(config.difficulty, Some(Difficulty::Hard));
+    }
+
+    #[test]
+    fn custom_bounds_accept_negative_and_64_bit_values() {
+        let config = parse(&["--min", "-5000000000", "--max", "9223372036854775807"]).unwrap();
+
+        assert_eq!(config.range, -5_000_000_000..=i64::MAX);
+        assert_eq!(config.max_attempts, None);
+    }
+
+    #[test]
+    fn custom_bounds_override_preset_range() {
+        let config = parse(&["-d", "easy", "--max", "20"]).unwrap();
+
+        assert_eq!(config.range, 1..=20);
+        assert_eq!(config.max_attempts, Some(5));
+        assert_eq!(config.label(), "custom");
+    }
+
+    #[test]
+    fn label_names_untouched_presets() {
+        assert_eq!(parse(&["-d", "easy"]).unwrap().label(), "easy");
+        assert_eq!(parse(&[]).unwrap().label(), "custom");
+    }
+
+    #[test]
+    fn max_guesses_overrides_preset_budget() {
+        assert_eq!(
+            parse(&["--max-guesses", "3"]).unwrap().max_attempts,
+            Some(3)
+        );
+        assert_eq!(
+            parse(&["-d", "hard", "--max-guesses", "50"])
+                .unwrap()
+                .max_attempts,
+            Some(50)
+        );
+        assert!(parse(&["--max-guesses", "0"]).is_err());
+    }
+
+    #[test]
+    fn evil_flag() {
+        assert!(parse(&["--evil"]).unwrap().evil);
+        assert_eq!(
+            parse(&["--evil", "-d", "hard"]).unwrap().label(),
+            "evil-hard"
+        );
+        assert!(!parse(&[]).unwrap().evil);
+    }
+
+    #[test]
+    fn scripted_json_output() {
+        let config = parse(&["--script", "-", "--json"]).unwrap();
+
+        assert_eq!(config.script.as_deref(), Some("-"));
+        assert!(config.json);
+        assert!(parse(&["--script"]).is_err());
+    }
+
+    #[test]
+    fn hints_need_a_real_secret() {
+        assert!(parse(&["--hints"]).unwrap().hints);
+        assert!(parse(&["--hints", "--evil"]).is_err());
+    }
+
+    #[test]
+    fn lang() {
+        assert_eq!(parse(&["--lang", "es"]).unwrap().lang, Some(Lang::Es));
+        assert_eq!(parse(&[]).unwrap().lang, None);
+        assert!(parse(&["--lang", "klingon"]).is_err());
+    }
+
+    #[test]
+    fn info_works_with_any_host() {
+        assert!(parse(&["--info", "--evil"]).unwrap().info);
+        assert!(!parse(&[]).unwrap().info);
+    }
+
+    #[test]
+    fn seed() {
+        assert_eq!(parse(&["--seed", "42"]).unwrap().seed, Some(42));
+        assert_eq!(parse(&[]).unwrap().seed, None);
+    }
+
+    #[test]
+    fn lists_optional_flags() {
+        assert!(parse(&["-d", "hard", "--lang", "fr"])
+            .unwrap()
+            .optional_flags()
+            .is_empty());
+        assert_eq!(
+            parse(&["--json", "--seed", "3", "--record", "x"])
+                .unwrap()
+                .optional_flags(),
+            ["--seed", "--json", "--record"]
+        );
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert!(parse(&["--min", "10", "--max", "1"]).is_err());
+        assert!(parse(&["--min", "ten"]).is_err());
+        assert!(parse(&["--max"]).is_err());
+        assert!(parse(&["--difficulty", "nightmare"]).is_err());
+        assert!(parse(&["--seed", "-1"]).is_err());
+        assert!(parse(&["--bogus"]).is_err());
+    }
+}
@@ -0,0 +1,319 @@
+//! The daily puzzle: one secret per UTC day, the same for everyone.
+//!
+//! The secret is a hash of the date, the range and an optional salt, so a
+//! team can share a salt to get a puzzle of its own. The hash is FNV-1a,
+//! which unlike `DefaultHasher` gives the same answer on every platform
+//! and Rust version.
+
+use crate::date::Date;
+use crate::score::range_size;
+use crate::storage::{self, clean};
+use std::cmp::Ordering;
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+const HEADER: &str = "# guessing_game daily results v1";
+
+/// The 64-bit FNV-1a hash of `bytes`.
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// The secret for `date` in `range`.
+pub fn secret(range: &RangeInclusive<i64>, date: Date, salt: &str) -> i64 {
+    let key = format!("{}|{}|{}|{}", date, range.start(), range.end(), salt);
+    let offset = u128::from(fnv1a(key.as_bytes())) % range_size(range);
+
+    (i128::from(*range.start()) + offset as i128) as i64
+}
+
+/// A spoiler-free summary such as `Daily 2026-10-17: 4 guesses ↑↓↑✔`.
+///
+/// Each arrow points the way the secret was from a guess, so it shows how
+/// the game went without giving away any numbers.
+pub fn share_line(date: Date, transcript: &[Ordering], won: bool) -> String {
+    summary(&format!("Daily {}", date), transcript, won)
+}
+
+/// Like [`share_line`], but marked so a replayed old puzzle cannot pass
+/// for an official result.
+pub fn practice_line(date: Date, transcript: &[Ordering], won: bool) -> String {
+    summary(&format!("Practice {}", date), transcript, won)
+}
+
+fn summary(title: &str, transcript: &[Ordering], won: bool) -> String {
+    let arrows: String = transcript
+        .iter()
+        .map(|ordering| match ordering {
+            Ordering::Less => '↑',
+            Ordering::Greater => '↓',
+            Ordering::Equal => '✔',
+        })
+        .collect();
+
+    if won {
+        format!("{}: {} guesses {}", title, transcript.len(), arrows)
+    } else {
+        format!("{}: X {}✘", title, arrows)
+    }
+}
+
+/// One player's submitted result for one day's puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DailyResult {
+    pub date: Date,
+    pub name: String,
+    pub salt: String,
+    /// `guess.cmp(&secret)` for each guess, in order.
+    pub transcript: Vec<Ordering>,
+    pub won: bool,
+}
+
+impl DailyResult {
+    pub fn share_line(&self) -> String {
+        share_line(self.date, &self.transcript, self.won)
+    }
+
+    fn to_line(&self) -> String {
+        let transcript: String = self
+            .transcript
+            .iter()
+            .map(|ordering| match ordering {
+                Ordering::Less => '<',
+                Ordering::Greater => '>',
+                Ordering::Equal => '=',
+            })
+            .collect();
+
+        format!(
+            "{}\t{}\t{}\t{}\t{}",
+            self.date,
+            clean(&self.name),
+            clean(&self.salt),
+            if self.won { "won" } else { "lost" },
+            transcript
+        )
+    }
+
+    fn from_line(line: &str) -> Option<DailyResult> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 5 {
+            return None;
+        }
+
+        let won = match fields[3] {
+            "won" => true,
+            "lost" => false,
+            _ => return None,
+        };
+        let transcript = fields[4]
+            .chars()
+            .map(|c| match c {
+                '<' => Some(Ordering::Less),
+                '>' => Some(Ordering::Greater),
+                '=' => Some(Ordering::Equal),
+                _ => None,
+            })
+            .collect::<Option<Vec<Ordering>>>()?;
+
+        Some(DailyResult {
+            date: fields[0].parse().ok()?,
+            name: fields[1].to_string(),
+            salt: fields[2].to_string(),
+            transcript,
+            won,
+        })
+    }
+}
+
+/// Every daily result submitted on this machine.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DailyLog {
+    results: Vec<DailyResult>,
+}
+
+impl DailyLog {
+    /// Where the log lives by default, inside [`storage::data_dir`].
+    pub fn default_path() -> Option<PathBuf> {
+        storage::data_dir().map(|dir| dir.join("daily.tsv"))
+    }
+
+    /// Parses a saved log, skipping any line that is damaged or cut short.
+    pub fn parse(text: &str) -> DailyLog {
+        let results = text
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(DailyResult::from_line)
+            .collect();
+
+        DailyLog { results }
+    }
+
+    /// Loads the log at `path`; a missing file is an empty log.
+    pub fn load(path: &Path) -> io::Result<DailyLog> {
+        storage::read_or_empty(path).map(|text| DailyLog::parse(&text))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        storage::write_atomic(path, &self.to_text())
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::from(HEADER);
+        text.push('\n');
+        for result in &self.results {
+            text.push_str(&result.to_line());
+            text.push('\n');
+        }
+        text
+    }
+
+    /// The result `name` already submitted for the puzzle, if any.
+    pub fn find(&self, date: Date, name: &str, salt: &str) -> Option<&DailyResult> {
+        self.results
+            .iter()
+            .find(|r| r.date == date && r.name == clean(name) && r.salt == clean(salt))
+    }
+
+    /// Adds a result, unless the player already has one for that puzzle.
+    /// Returns whether it was added.
+    pub fn submit(&mut self, result: DailyResult) -> bool {
+        if self.find(result.date, &result.name, &result.salt).is_some() {
+            return false;
+        }
+
+        self.results.push(result);
+        true
+    }
+
+    /// Adds a result, replacing the player's earlier one for that puzzle.
+    ///
+    /// A game in progress is saved this way after every guess, so quitting
+    /// part-way leaves a lost result behind rather than a second try.
+    pub fn update(&mut self, result: DailyResult) {
+        let (name, salt) = (clean(&result.name), clean(&result.salt));
+        self.results
+            .retain(|r| !(r.date == result.date && r.name == name && r.salt == salt));
+        self.results.push(result);
+    }
+
+    pub fn results(&self) -> &[DailyResult] {
+        &self.results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> Date {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn fnv1a_matches_reference_values() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn secret_changes_with_date_and_salt() {
+        let range = 1..=100;
+        let today = secret(&range, date("2026-10-17"), "");
+
+        assert!(range.contains(&today));
+        assert_eq!(secret(&range, date("2026-10-17"), ""), today);
+
+        let others: Vec<i64> = (1..=9)
+            .map(|day| secret(&range, date(&format!("2026-11-0{}", day)), ""))
+            .collect();
+        assert!(others.iter().any(|&other| other != today));
+
+        let salted: Vec<i64> = (0..10)
+            .map(|n| secret(&range, date("2026-10-17"), &n.to_string()))
+            .collect();
+        assert!(salted.iter().any(|&other| other != today));
+    }
+
+    #[test]
+    fn secret_stays_in_extreme_ranges() {
+        let range = i64::MIN..=i64::MAX;
+        secret(&range, date("2026-10-17"), "team");
+
+        assert_eq!(secret(&(-3..=-3), date("2026-10-17"), "x"), -3);
+    }
+
+    #[test]
+    fn share_line_has_no_numbers_from_the_game() {
+        let transcript = [Ordering::Less, Ordering::Greater, Ordering::Equal];
+
+        assert_eq!(
+            share_line(date("2026-10-17"), &transcript, true),
+            "Daily 2026-10-17: 3 guesses ↑↓✔"
+        );
+        assert_eq!(
+            share_line(date("2026-10-17"), &transcript[..2], false),
+            "Daily 2026-10-17: X ↑↓✘"
+        );
+        assert_eq!(
+            practice_line(date("2026-10-16"), &transcript, true),
+            "Practice 2026-10-16: 3 guesses ↑↓✔"
+        );
+    }
+
+    #[test]
+    fn one_result_per_player_per_puzzle() {
+        let result = DailyResult {
+            date: date("2026-10-17"),
+            name: "ada".to_string(),
+            salt: String::new(),
+            transcript: vec![Ordering::Greater, Ordering::Equal],
+            won: true,
+        };
+        let mut log = DailyLog::default();
+
+        assert!(log.submit(result.clone()));
+        assert!(!log.submit(result.clone()));
+        assert!(log.submit(DailyResult {
+            salt: "team".to_string(),
+            ..result.clone()
+        }));
+        assert!(log.submit(DailyResult {
+            date: date("2026-10-18"),
+            ..result.clone()
+        }));
+
+        assert_eq!(DailyLog::parse(&log.to_text()), log);
+        assert_eq!(log.find(result.date, "ada", ""), Some(&result));
+    }
+
+    #[test]
+    fn update_replaces_progress() {
+        let started = DailyResult {
+            date: date("2026-10-17"),
+            name: "eve".to_string(),
+            salt: String::new(),
+            transcript: Vec::new(),
+            won: false,
+        };
+        let finished = DailyResult {
+            transcript: vec![Ordering::Equal],
+            won: true,
+            ..started.clone()
+        };
+        let mut log = DailyLog::default();
+
+        log.update(started.clone());
+        assert!(!log.submit(finished.clone()));
+        log.update(finished.clone());
+
+        assert_eq!(log.results(), [finished]);
+    }
+}
@@ -0,0 +1,139 @@
+//! The adversarial host, which never commits to a secret up front.
+//!
+//! Instead it keeps the [`Interval`] of numbers that fit everything it has
+//! said, and answers each guess so that the larger part of that interval
+//! survives. Only when a single number is left can a guess win.
+
+use crate::interval::Interval;
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// The answer that keeps the most candidates alive, as `guess.cmp(&secret)`.
+///
+/// Ties keep the upper side. A guess outside `candidates` gets the only
+/// answer that stays consistent.
+pub fn evil_answer(candidates: &Interval, guess: i64) -> Ordering {
+    let (low, high) = candidates
+        .low()
+        .zip(candidates.high())
+        .expect("no candidates left");
+
+    if guess < low {
+        return Ordering::Less;
+    }
+    if guess > high {
+        return Ordering::Greater;
+    }
+
+    let below = guess as i128 - low as i128;
+    let above = high as i128 - guess as i128;
+    if below == 0 && above == 0 {
+        Ordering::Equal
+    } else if above >= below {
+        Ordering::Less
+    } else {
+        Ordering::Greater
+    }
+}
+
+/// Why a revealed secret does not fit a game's transcript.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriptError {
+    SecretOutsideRange {
+        secret: i64,
+    },
+    /// The answer to guess number `index` (from zero) was not the truth.
+    WrongAnswer {
+        index: usize,
+        guess: i64,
+        claimed: Ordering,
+        actual: Ordering,
+    },
+}
+
+impl fmt::Display for TranscriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TranscriptError::SecretOutsideRange { secret } => {
+                write!(f, "the secret {} is outside the game's range", secret)
+            }
+            TranscriptError::WrongAnswer {
+                index,
+                guess,
+                claimed,
+                actual,
+            } => write!(
+                f,
+                "guess #{} ({}) was answered {:?} but the secret makes it {:?}",
+                index + 1,
+                guess,
+                claimed,
+                actual
+            ),
+        }
+    }
+}
+
+impl Error for TranscriptError {}
+
+/// Checks that `secret` agrees with every `(guess, guess.cmp(&secret))`
+/// pair in `transcript`.
+pub fn verify_transcript(
+    range: &RangeInclusive<i64>,
+    transcript: &[(i64, Ordering)],
+    secret: i64,
+) -> Result<(), TranscriptError> {
+    if !range.contains(&secret) {
+        return Err(TranscriptError::SecretOutsideRange { secret });
+    }
+
+    for (index, &(guess, claimed)) in transcript.iter().enumerate() {
+        let actual = guess.cmp(&secret);
+        if actual != claimed {
+            return Err(TranscriptError::WrongAnswer {
+                index,
+                guess,
+                claimed,
+                actual,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_larger_side() {
+        let candidates = Interval::new(&(1..=100));
+
+        assert_eq!(evil_answer(&candidates, 30), Ordering::Less);
+        assert_eq!(evil_answer(&candidates, 70), Ordering::Greater);
+        assert_eq!(evil_answer(&candidates, 1), Ordering::Less);
+        assert_eq!(evil_answer(&candidates, 0), Ordering::Less);
+        assert_eq!(evil_answer(&candidates, 500), Ordering::Greater);
+        assert_eq!(evil_answer(&Interval::new(&(7..=7)), 7), Ordering::Equal);
+    }
+
+    #[test]
+    fn verifier_spots_lies() {
+        let transcript = [(50, Ordering::Less), (75, Ordering::Greater)];
+
+        assert_eq!(verify_transcript(&(1..=100), &transcript, 60), Ok(()));
+        assert_eq!(
+            verify_transcript(&(1..=100), &transcript, 80),
+            Err(TranscriptError::WrongAnswer {
+                index: 1,
+                guess: 75,
+                claimed: Ordering::Greater,
+                actual: Ordering::Less,
+            })
+        );
+        assert!(verify_transcript(&(1..=100), &[], 0).is_err());
+    }
+}
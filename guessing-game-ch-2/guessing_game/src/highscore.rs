@@ -0,0 +1,276 @@
+use crate::date::Date;
+use crate::storage::{self, clean};
+use std::cmp::Reverse;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const HEADER: &str = "# guessing_game high scores v1";
+
+/// The most games kept for each difficulty; adding one more drops the
+/// worst, so the file cannot grow without limit.
+pub const MAX_PER_DIFFICULTY: usize = 100;
+
+/// One finished game in the high-score table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub name: String,
+    /// A preset name such as `normal`, or `custom` for hand-picked bounds.
+    pub difficulty: String,
+    pub min: i64,
+    pub max: i64,
+    pub guesses: u32,
+    pub won: bool,
+    pub score: u32,
+    pub duration: Duration,
+    pub date: Date,
+}
+
+impl Entry {
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.date,
+            clean(&self.name),
+            clean(&self.difficulty),
+            self.min,
+            self.max,
+            self.guesses,
+            if self.won { "won" } else { "lost" },
+            self.score,
+            self.duration.as_millis()
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Entry> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 9 {
+            return None;
+        }
+
+        let won = match fields[6] {
+            "won" => true,
+            "lost" => false,
+            _ => return None,
+        };
+
+        Some(Entry {
+            date: fields[0].parse().ok()?,
+            name: fields[1].to_string(),
+            difficulty: fields[2].to_string(),
+            min: fields[3].parse().ok()?,
+            max: fields[4].parse().ok()?,
+            guesses: fields[5].parse().ok()?,
+            won,
+            score: fields[7].parse().ok()?,
+            duration: Duration::from_millis(fields[8].parse().ok()?),
+        })
+    }
+}
+
+/// The saved table of finished games.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HighScores {
+    entries: Vec<Entry>,
+}
+
+impl HighScores {
+    /// Where the table lives by default, inside [`storage::data_dir`].
+    pub fn default_path() -> Option<PathBuf> {
+        storage::data_dir().map(|dir| dir.join("scores.tsv"))
+    }
+
+    /// Parses a saved table, skipping any line that is damaged or cut short.
+    pub fn parse(text: &str) -> HighScores {
+        let entries = text
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(Entry::from_line)
+            .collect();
+
+        HighScores { entries }
+    }
+
+    /// Loads the table at `path`; a missing file is an empty table.
+    pub fn load(path: &Path) -> io::Result<HighScores> {
+        storage::read_or_empty(path).map(|text| HighScores::parse(&text))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        storage::write_atomic(path, &self.to_text())
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::from(HEADER);
+        text.push('\n');
+        for entry in &self.entries {
+            text.push_str(&entry.to_line());
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Adds a game, then drops the worst at its difficulty if there are
+    /// more than [`MAX_PER_DIFFICULTY`]. Losses are dropped before wins,
+    /// and the oldest of equally good games before the newer ones.
+    pub fn add(&mut self, entry: Entry) {
+        let difficulty = entry.difficulty.clone();
+        self.entries.push(entry);
+
+        let mut ranked: Vec<usize> = (0..self.entries.len())
+            .filter(|&i| self.entries[i].difficulty == difficulty)
+            .collect();
+        if ranked.len() <= MAX_PER_DIFFICULTY {
+            return;
+        }
+
+        // Best first; among equal games, newest first.
+        ranked.sort_by_key(|&i| {
+            let entry = &self.entries[i];
+            (!entry.won, rank(entry), Reverse(i))
+        });
+        let mut keep = vec![true; self.entries.len()];
+        for &i in &ranked[MAX_PER_DIFFICULTY..] {
+            keep[i] = false;
+        }
+        let mut keep = keep.into_iter();
+        self.entries.retain(|_| keep.next().unwrap());
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Every difficulty that has at least one entry, in first-seen order.
+    pub fn difficulties(&self) -> Vec<&str> {
+        let mut difficulties: Vec<&str> = Vec::new();
+        for entry in &self.entries {
+            if !difficulties.contains(&entry.difficulty.as_str()) {
+                difficulties.push(&entry.difficulty);
+            }
+        }
+        difficulties
+    }
+
+    /// The best `n` games won at `difficulty`.
+    ///
+    /// Games rank by score, then fewest guesses, then fastest time.
+    pub fn top(&self, difficulty: &str, n: usize) -> Vec<&Entry> {
+        let mut entries: Vec<&Entry> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.won && entry.difficulty == difficulty)
+            .collect();
+        entries.sort_by_key(|entry| rank(entry));
+        entries.truncate(n);
+        entries
+    }
+}
+
+/// Sorts better games first: by score, then fewest guesses, then time.
+fn rank(entry: &Entry) -> (Reverse<u32>, u32, Duration) {
+    (Reverse(entry.score), entry.guesses, entry.duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, guesses: u32, score: u32, millis: u64) -> Entry {
+        Entry {
+            name: name.to_string(),
+            difficulty: "normal".to_string(),
+            min: 1,
+            max: 100,
+            guesses,
+            won: true,
+            score,
+            duration: Duration::from_millis(millis),
+            date: "2026-10-17".parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let mut scores = HighScores::default();
+        scores.add(entry("alice", 5, 100, 1200));
+        scores.add(Entry {
+            won: false,
+            difficulty: "custom".to_string(),
+            min: -5_000_000_000,
+            ..entry("bob\tby", 10, 0, 30)
+        });
+
+        let parsed = HighScores::parse(&scores.to_text());
+
+        assert_eq!(parsed.entries().len(), 2);
+        assert_eq!(parsed.entries()[0], scores.entries()[0]);
+        assert_eq!(parsed.entries()[1].name, "bob by");
+        assert_eq!(parsed.entries()[1].min, -5_000_000_000);
+    }
+
+    #[test]
+    fn skips_corrupted_and_truncated_lines() {
+        let mut text = HighScores {
+            entries: vec![entry("alice", 5, 100, 1200)],
+        }
+        .to_text();
+        text.push_str("garbage\n\u{0}\u{0}\n2026-10-17\tbob\tnormal\t1\t100\t3\two");
+
+        let parsed = HighScores::parse(&text);
+
+        assert_eq!(parsed.entries().len(), 1);
+        assert_eq!(parsed.entries()[0].name, "alice");
+    }
+
+    #[test]
+    fn keeps_only_the_best_games_per_difficulty() {
+        let mut scores = HighScores::default();
+        scores.add(Entry {
+            difficulty: "hard".to_string(),
+            ..entry("other", 9, 10, 10)
+        });
+        scores.add(Entry {
+            won: false,
+            ..entry("loser", 10, 0, 10)
+        });
+        for i in 0..MAX_PER_DIFFICULTY as u32 {
+            scores.add(entry(&format!("player{}", i), 5, 50 + i % 10, 1000));
+        }
+
+        assert_eq!(scores.entries().len(), MAX_PER_DIFFICULTY + 1);
+        assert!(scores.entries().iter().all(|entry| entry.won));
+
+        scores.add(entry("best", 1, 100, 10));
+        scores.add(entry("worst", 9, 1, 10));
+
+        let names: Vec<&str> = scores.entries().iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names.len(), MAX_PER_DIFFICULTY + 1);
+        assert_eq!(names[0], "other");
+        assert!(names.contains(&"best"));
+        assert!(!names.contains(&"worst"));
+        assert_eq!(scores.top("normal", 1)[0].name, "best");
+    }
+
+    #[test]
+    fn top_ranks_by_score_then_guesses_then_time() {
+        let mut scores = HighScores::default();
+        scores.add(entry("slow", 7, 100, 9000));
+        scores.add(entry("fast", 7, 100, 1000));
+        scores.add(entry("lucky", 2, 100, 5000));
+        scores.add(entry("meh", 14, 50, 100));
+        scores.add(Entry {
+            won: false,
+            ..entry("loser", 10, 0, 10)
+        });
+
+        let names: Vec<&str> = scores
+            .top("normal", 3)
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect();
+
+        assert_eq!(names, ["lucky", "fast", "slow"]);
+        assert!(scores.top("hard", 3).is_empty());
+    }
+}
@@ -0,0 +1,230 @@
+//! Optional hints on top of the plain "too small"/"too big" feedback.
+//!
+//! Everything here is pure: it looks at the secret and the guesses and
+//! says what the player should be told, leaving the printing to the caller.
+
+use crate::score::range_size;
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// How close a guess landed to the secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Proximity {
+    Burning,
+    Warm,
+    Cold,
+}
+
+impl fmt::Display for Proximity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Proximity::Burning => "Burning",
+            Proximity::Warm => "Warm",
+            Proximity::Cold => "Cold",
+        })
+    }
+}
+
+/// Classifies how far `guess` is from `secret`, relative to the range.
+///
+/// Within 2% of the range (or 1) is burning, within 10% (or 2) is warm,
+/// anything further is cold.
+pub fn proximity(range: &RangeInclusive<i64>, secret: i64, guess: i64) -> Proximity {
+    let distance = distance(secret, guess);
+    let size = range_size(range);
+
+    if distance <= (size / 50).max(1) {
+        Proximity::Burning
+    } else if distance <= (size / 10).max(2) {
+        Proximity::Warm
+    } else {
+        Proximity::Cold
+    }
+}
+
+/// Whether a guess got nearer to the secret than the one before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Closer,
+    Farther,
+    Same,
+}
+
+impl fmt::Display for Trend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Trend::Closer => "closer than last time",
+            Trend::Farther => "farther than last time",
+            Trend::Same => "as far as last time",
+        })
+    }
+}
+
+pub fn trend(secret: i64, previous: i64, guess: i64) -> Trend {
+    let before = distance(secret, previous);
+    let now = distance(secret, guess);
+
+    match now.cmp(&before) {
+        std::cmp::Ordering::Less => Trend::Closer,
+        std::cmp::Ordering::Greater => Trend::Farther,
+        std::cmp::Ordering::Equal => Trend::Same,
+    }
+}
+
+fn distance(a: i64, b: i64) -> u128 {
+    (a as i128 - b as i128).unsigned_abs()
+}
+
+/// A fact about the secret the player can buy with score points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaidHint {
+    /// Whether the secret is even or odd.
+    Parity,
+    /// Whether the secret is a multiple of the given number.
+    DivisibleBy(u32),
+}
+
+impl PaidHint {
+    /// The points taken off the final score.
+    pub fn cost(self) -> u32 {
+        match self {
+            PaidHint::Parity => 15,
+            PaidHint::DivisibleBy(_) => 10,
+        }
+    }
+
+    /// The fact this hint reveals about `secret`.
+    pub fn reveal(self, secret: i64) -> Fact {
+        match self {
+            PaidHint::Parity if secret % 2 == 0 => Fact::Even,
+            PaidHint::Parity => Fact::Odd,
+            PaidHint::DivisibleBy(n) if secret % i64::from(n) == 0 => Fact::DivisibleBy(n),
+            PaidHint::DivisibleBy(n) => Fact::NotDivisibleBy(n),
+        }
+    }
+}
+
+impl FromStr for PaidHint {
+    type Err = HintError;
+
+    /// Parses `parity` or `div N`, as typed after `hint`.
+    fn from_str(s: &str) -> Result<PaidHint, HintError> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+
+        match words.as_slice() {
+            ["parity"] => Ok(PaidHint::Parity),
+            ["div", n] => match n.parse() {
+                Ok(n) if n >= 2 => Ok(PaidHint::DivisibleBy(n)),
+                _ => Err(HintError::NotADivisor(n.to_string())),
+            },
+            _ => Err(HintError::Unknown),
+        }
+    }
+}
+
+/// What a [`PaidHint`] told the player about the secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fact {
+    Even,
+    Odd,
+    DivisibleBy(u32),
+    NotDivisibleBy(u32),
+}
+
+impl fmt::Display for Fact {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Fact::Even => write!(f, "The secret number is even."),
+            Fact::Odd => write!(f, "The secret number is odd."),
+            Fact::DivisibleBy(n) => write!(f, "The secret number is divisible by {}.", n),
+            Fact::NotDivisibleBy(n) => {
+                write!(f, "The secret number is not divisible by {}.", n)
+            }
+        }
+    }
+}
+
+/// Why a hint request could not be understood.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HintError {
+    /// `div` followed by something other than a whole number of 2 or more.
+    NotADivisor(String),
+    Unknown,
+}
+
+impl fmt::Display for HintError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HintError::NotADivisor(n) => write!(f, "`{}` is not a whole number of 2 or more.", n),
+            HintError::Unknown => write!(f, "Hints are `hint parity` or `hint div N`."),
+        }
+    }
+}
+
+impl Error for HintError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proximity_scales_with_range() {
+        let range = 1..=100;
+
+        assert_eq!(proximity(&range, 50, 52), Proximity::Burning);
+        assert_eq!(proximity(&range, 50, 41), Proximity::Warm);
+        assert_eq!(proximity(&range, 50, 90), Proximity::Cold);
+
+        let wide = 1..=1_000_000;
+        assert_eq!(proximity(&wide, 500_000, 515_000), Proximity::Burning);
+        assert_eq!(proximity(&wide, 500_000, 590_000), Proximity::Warm);
+    }
+
+    #[test]
+    fn tiny_ranges_still_get_burning_and_warm() {
+        let range = 1..=10;
+
+        assert_eq!(proximity(&range, 5, 6), Proximity::Burning);
+        assert_eq!(proximity(&range, 5, 7), Proximity::Warm);
+        assert_eq!(proximity(&range, 5, 10), Proximity::Cold);
+    }
+
+    #[test]
+    fn proximity_survives_extreme_values() {
+        let range = i64::MIN..=i64::MAX;
+
+        assert_eq!(proximity(&range, i64::MIN, i64::MAX), Proximity::Cold);
+    }
+
+    #[test]
+    fn trend_compares_distances() {
+        assert_eq!(trend(50, 10, 40), Trend::Closer);
+        assert_eq!(trend(50, 40, 70), Trend::Farther);
+        assert_eq!(trend(50, 40, 60), Trend::Same);
+    }
+
+    #[test]
+    fn paid_hints_reveal_facts() {
+        assert_eq!(PaidHint::Parity.reveal(42), Fact::Even);
+        assert_eq!(PaidHint::Parity.reveal(-7), Fact::Odd);
+        assert_eq!(PaidHint::DivisibleBy(3).reveal(42), Fact::DivisibleBy(3));
+        assert_eq!(
+            PaidHint::DivisibleBy(5).reveal(42).to_string(),
+            "The secret number is not divisible by 5."
+        );
+    }
+
+    #[test]
+    fn parses_paid_hints() {
+        assert_eq!("parity".parse(), Ok(PaidHint::Parity));
+        assert_eq!(" div 7 ".parse(), Ok(PaidHint::DivisibleBy(7)));
+        assert_eq!(
+            "div 1".parse::<PaidHint>(),
+            Err(HintError::NotADivisor("1".to_string()))
+        );
+        assert!("div x".parse::<PaidHint>().is_err());
+        assert_eq!("secret please".parse::<PaidHint>(), Err(HintError::Unknown));
+    }
+}
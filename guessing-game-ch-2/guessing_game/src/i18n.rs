@@ -0,0 +1,1157 @@
+//! Translations of what the game says to the player.
+//!
+//! English is written out in full. Every other language is a table that
+//! may lack some keys; anything missing is shown in English instead.
+//! Messages use `{}` placeholders, filled in order by [`message`].
+
+use crate::bulls_cows::{CodeError, Score};
+use crate::hint::{Fact, HintError, Proximity, Trend};
+use crate::info::Rating;
+use crate::replay::{ReplayError, VerifyError};
+use crate::reverse::{Answer, Cheating};
+use crate::words::WordError;
+use crate::InputError;
+use std::cmp::Ordering;
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    En,
+    Es,
+    Fr,
+}
+
+impl Lang {
+    pub const ALL: [Lang; 3] = [Lang::En, Lang::Es, Lang::Fr];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::En => "en",
+            Lang::Es => "es",
+            Lang::Fr => "fr",
+        }
+    }
+
+    /// The language named by a locale such as `fr_FR.UTF-8`, if supported.
+    pub fn from_locale(locale: &str) -> Option<Lang> {
+        let code = locale.split(['_', '.', '@', '-']).next().unwrap_or("");
+        code.parse().ok()
+    }
+
+    /// The language from `$LANG`, or English when it is unset or unknown.
+    pub fn from_env() -> Lang {
+        env::var("LANG")
+            .ok()
+            .and_then(|locale| Lang::from_locale(&locale))
+            .unwrap_or(Lang::En)
+    }
+}
+
+impl fmt::Display for Lang {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Lang {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Lang, String> {
+        Lang::ALL
+            .into_iter()
+            .find(|lang| lang.code().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown language `{}` (expected en, es or fr)", s))
+    }
+}
+
+/// Declares `Key` with one variant per name, and `Key::ALL` listing them
+/// all, so a new key can never be left out of `ALL`.
+macro_rules! keys {
+    ($($key:ident),* $(,)?) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Key {
+            $($key),*
+        }
+
+        impl Key {
+            pub const ALL: &'static [Key] = &[$(Key::$key),*];
+        }
+    };
+}
+
+keys! {
+    GuessTheNumber,
+    SecretBetween,
+    GuessesAllowed,
+    HintHelp,
+    PleaseGuess,
+    NoMoreInput,
+    YouGuessed,
+    TooSmallRange,
+    TooBigRange,
+    TooSmall,
+    TooBig,
+    YouWin,
+    YouLose,
+    EvilConsistent,
+    EvilCheated,
+    GuessesSummary,
+    HintsCost,
+    Score,
+    InputEmpty,
+    InputNotANumber,
+    InputNegative,
+    InputOutOfRange,
+    DailyPuzzle,
+    DailyPractice,
+    DailyAbandoned,
+    AlreadyPlayed,
+    HintBought,
+    FactEven,
+    FactOdd,
+    FactDivisible,
+    FactNotDivisible,
+    HintNotADivisor,
+    HintUnknown,
+    Burning,
+    Warm,
+    Cold,
+    Closer,
+    Farther,
+    AsFar,
+    Proximity,
+    ProximityTrend,
+    CandidatesLeft,
+    BitsEarned,
+    ColumnGuess,
+    ColumnResult,
+    ColumnLeft,
+    ColumnBits,
+    ColumnIdeal,
+    ColumnRating,
+    Less,
+    Greater,
+    Equal,
+    Lucky,
+    Ideal,
+    Good,
+    Poor,
+    Wasted,
+    TotalBits,
+    NoGamesYet,
+    StatsPlayed,
+    StatsGuesses,
+    StatsStreak,
+    Replaying,
+    ReverseIntro,
+    ReverseAsk,
+    ReverseFound,
+    InputClosed,
+    NotAnAnswer,
+    Higher,
+    Lower,
+    Correct,
+    CheatContradiction,
+    CheatOutsideRange,
+    CowsIntro,
+    CowsDigits,
+    CowsSolverGuess,
+    CowsScore,
+    CowsNoMoreInput,
+    CowsLose,
+    CowsSolved,
+    CowsWin,
+    CodeWrongLength,
+    CodeNotADigit,
+    CodeOutOfRange,
+    CodeRepeatedDigit,
+    WordsIntro,
+    WordsLength,
+    WordsNoMoreInput,
+    WordsLose,
+    WordWrongLength,
+    WordNotInList,
+    PartyHotSeat,
+    PartyRace,
+    PartyRaceBetween,
+    PartyPrompt,
+    PartyGotIt,
+    PartyOutOfGuesses,
+    PartyRanking,
+    PartyStanding,
+    PartyWon,
+    PartyLost,
+    NoScoresYet,
+    ScoresEntry,
+    CouldNotRead,
+    ScoresUnreadable,
+    VerifyMatches,
+    GameWon,
+    GameLost,
+    GameUnfinished,
+    ReplayNotAReplay,
+    ReplayNewerVersion,
+    ReplayMissing,
+    ReplayBadLine,
+    VerifyMismatch,
+    VerifyImpossible,
+    SimulateIntro,
+    SimulateSummary,
+    ServeIntro,
+    ServeCannotListen,
+    ServeStopped,
+    AcceptFailed,
+    WordsNotFound,
+    WordsNoneOfLength,
+    BuiltinWordList,
+}
+
+fn english(key: Key) -> &'static str {
+    match key {
+        Key::GuessTheNumber => "Guess the number! (seed {})",
+        Key::SecretBetween => "The secret number is between {} and {}.",
+        Key::GuessesAllowed => "You have {} guesses.",
+        Key::HintHelp => "Type `hint parity` or `hint div N` to buy a hint.",
+        Key::PleaseGuess => "Please input your guess.",
+        Key::NoMoreInput => "No more input; the secret number was {}.",
+        Key::YouGuessed => "You guessed: {}",
+        Key::TooSmallRange => "Too small! (range {} to {})",
+        Key::TooBigRange => "Too big! (range {} to {})",
+        Key::TooSmall => "Too small!",
+        Key::TooBig => "Too big!",
+        Key::YouWin => "You win!",
+        Key::YouLose => "You lose! The secret number was {}.",
+        Key::EvilConsistent => "Every answer was consistent with {}.",
+        Key::EvilCheated => "The host cheated: {}",
+        Key::GuessesSummary => "Guesses: {} (best possible: {})",
+        Key::HintsCost => "Hints cost you {} points.",
+        Key::Score => "Score: {}/{}",
+        Key::InputEmpty => "Please type a number.",
+        Key::InputNotANumber => "`{}` is not a number.",
+        Key::InputNegative => "{} is negative; the secret is between {} and {}.",
+        Key::InputOutOfRange => "{} is out of range; the secret is between {} and {}.",
+        Key::DailyPuzzle => "Daily puzzle for {}!",
+        Key::DailyPractice => "Practice puzzle for {}! Practice games are not recorded.",
+        Key::DailyAbandoned => "No more input; today's puzzle counts as lost.",
+        Key::AlreadyPlayed => "{} already played the puzzle for {}.",
+        Key::HintBought => "{} (-{} points)",
+        Key::FactEven => "The secret number is even.",
+        Key::FactOdd => "The secret number is odd.",
+        Key::FactDivisible => "The secret number is divisible by {}.",
+        Key::FactNotDivisible => "The secret number is not divisible by {}.",
+        Key::HintNotADivisor => "`{}` is not a whole number of 2 or more.",
+        Key::HintUnknown => "Hints are `hint parity` or `hint div N`.",
+        Key::Burning => "Burning",
+        Key::Warm => "Warm",
+        Key::Cold => "Cold",
+        Key::Closer => "closer than last time",
+        Key::Farther => "farther than last time",
+        Key::AsFar => "as far as last time",
+        Key::Proximity => "{}.",
+        Key::ProximityTrend => "{}, and {}.",
+        Key::CandidatesLeft => "Candidates left: {} to {} ({} numbers).",
+        Key::BitsEarned => "That guess earned {} bits; bisection guarantees {}.",
+        Key::ColumnGuess => "guess",
+        Key::ColumnResult => "result",
+        Key::ColumnLeft => "left",
+        Key::ColumnBits => "bits",
+        Key::ColumnIdeal => "ideal",
+        Key::ColumnRating => "rating",
+        Key::Less => "Less",
+        Key::Greater => "Greater",
+        Key::Equal => "Equal",
+        Key::Lucky => "lucky",
+        Key::Ideal => "ideal",
+        Key::Good => "good",
+        Key::Poor => "poor",
+        Key::Wasted => "wasted",
+        Key::TotalBits => "Total: {} bits.",
+        Key::NoGamesYet => "No games played yet.",
+        Key::StatsPlayed => "Played {}, won {} ({}%)",
+        Key::StatsGuesses => "Guesses to win: mean {}, median {}",
+        Key::StatsStreak => "Streak: {} now, {} best",
+        Key::Replaying => "Replaying seed {}: the secret number is between {} and {}.",
+        Key::ReverseIntro => "Think of a number between {} and {}, and I will guess it.",
+        Key::ReverseAsk => "Is it {}? (higher/lower/correct)",
+        Key::ReverseFound => "Got it! Your number is {}. I needed {} guesses.",
+        Key::InputClosed => "No more input.",
+        Key::NotAnAnswer => "`{}` is not an answer; type higher, lower or correct.",
+        Key::Higher => "higher",
+        Key::Lower => "lower",
+        Key::Correct => "correct",
+        Key::CheatContradiction => "Cheater! You said {} than {} and then {} than {}.",
+        Key::CheatOutsideRange => {
+            "Cheater! You said {} than {}, but your number is between {} and {}."
+        }
+        Key::CowsIntro => "Bulls and cows! (seed {})",
+        Key::CowsDigits => "The secret has {} different digits.",
+        Key::CowsSolverGuess => "I guess {}.",
+        Key::CowsScore => "{} bulls, {} cows",
+        Key::CowsNoMoreInput => "No more input; the secret was {}.",
+        Key::CowsLose => "You lose! The secret was {}.",
+        Key::CowsSolved => "Solved in {} guesses.",
+        Key::CowsWin => "You win in {} guesses!",
+        Key::CodeWrongLength => "The code has {} digits, but you typed {}.",
+        Key::CodeNotADigit => "`{}` is not a digit.",
+        Key::CodeOutOfRange => "{} is not a digit from 0 to 9.",
+        Key::CodeRepeatedDigit => "The digit {} appears more than once; digits must differ.",
+        Key::WordsIntro => "Guess the word! (seed {})",
+        Key::WordsLength => "The secret word has {} letters. You have {} guesses.",
+        Key::WordsNoMoreInput => "No more input; the secret word was {}.",
+        Key::WordsLose => "You lose! The secret word was {}.",
+        Key::WordWrongLength => "The word has {} letters, but you typed {}.",
+        Key::WordNotInList => "`{}` is not in the word list.",
+        Key::PartyHotSeat => "Hot-seat game for {}! (seed {})",
+        Key::PartyRace => "Race for {}! (seed {})",
+        Key::PartyRaceBetween => "Everyone's secret number is between {} and {}.",
+        Key::PartyPrompt => "{}, please input your guess.",
+        Key::PartyGotIt => "{} got it!",
+        Key::PartyOutOfGuesses => "{} is out of guesses; the secret number was {}.",
+        Key::PartyRanking => "Final ranking:",
+        Key::PartyStanding => "{} after {} guesses: {}",
+        Key::PartyWon => "won",
+        Key::PartyLost => "lost",
+        Key::NoScoresYet => "No high scores yet.",
+        Key::ScoresEntry => "{} guesses  {} to {}  {}s  {}  score {}",
+        Key::CouldNotRead => "Could not read {}: {}",
+        Key::ScoresUnreadable => "warning: could not read high scores: {}",
+        Key::VerifyMatches => "{}: all {} guesses match the engine (seed {}, {}).",
+        Key::GameWon => "won",
+        Key::GameLost => "lost",
+        Key::GameUnfinished => "unfinished",
+        Key::ReplayNotAReplay => "not a guessing_game replay",
+        Key::ReplayNewerVersion => "replay format v{} is newer than this program understands (v{})",
+        Key::ReplayMissing => "the replay has no `{}` line",
+        Key::ReplayBadLine => "line {} is damaged: `{}`",
+        Key::VerifyMismatch => "guess #{} ({}) was recorded as {} but the engine says {}",
+        Key::VerifyImpossible => "guess #{} ({}) could not have been made",
+        Key::SimulateIntro => "Simulating {} games from {} to {} (seed {})",
+        Key::SimulateSummary => "{}: mean {} guesses, max {}, won {}/{}, mean score {}",
+        Key::ServeIntro => "Serving games from {} to {} on {} (seed {})",
+        Key::ServeCannotListen => "Could not listen on {}: {}",
+        Key::ServeStopped => "Server stopped: {}",
+        Key::AcceptFailed => "warning: could not accept a connection: {}",
+        Key::WordsNotFound => "{} not found.",
+        Key::WordsNoneOfLength => "{} has no {}-letter words.",
+        Key::BuiltinWordList => "The built-in word list",
+    }
+}
+
+const SPANISH: &[(Key, &str)] = &[
+    (Key::GuessTheNumber, "¡Adivina el número! (semilla {})"),
+    (Key::SecretBetween, "El número secreto está entre {} y {}."),
+    (Key::GuessesAllowed, "Tienes {} intentos."),
+    (
+        Key::HintHelp,
+        "Escribe `hint parity` o `hint div N` para comprar una pista.",
+    ),
+    (Key::PleaseGuess, "Por favor, introduce tu número."),
+    (
+        Key::NoMoreInput,
+        "No hay más entrada; el número secreto era {}.",
+    ),
+    (Key::YouGuessed, "Has dicho: {}"),
+    (Key::TooSmallRange, "¡Demasiado pequeño! (rango {} a {})"),
+    (Key::TooBigRange, "¡Demasiado grande! (rango {} a {})"),
+    (Key::TooSmall, "¡Demasiado pequeño!"),
+    (Key::TooBig, "¡Demasiado grande!"),
+    (Key::YouWin, "¡Has ganado!"),
+    (Key::YouLose, "¡Has perdido! El número secreto era {}."),
+    (
+        Key::EvilConsistent,
+        "Todas las respuestas eran coherentes con {}.",
+    ),
+    (Key::EvilCheated, "El anfitrión hizo trampa: {}"),
+    (Key::GuessesSummary, "Intentos: {} (mejor posible: {})"),
+    (Key::HintsCost, "Las pistas te costaron {} puntos."),
+    (Key::Score, "Puntuación: {}/{}"),
+    (Key::InputEmpty, "Por favor, escribe un número."),
+    (Key::InputNotANumber, "`{}` no es un número."),
+    (
+        Key::InputNegative,
+        "{} es negativo; el número secreto está entre {} y {}.",
+    ),
+    (
+        Key::InputOutOfRange,
+        "{} está fuera de rango; el número secreto está entre {} y {}.",
+    ),
+    (Key::DailyPuzzle, "¡Reto diario del {}!"),
+    (
+        Key::DailyPractice,
+        "¡Reto de práctica del {}! Las partidas de práctica no se guardan.",
+    ),
+    (
+        Key::DailyAbandoned,
+        "No hay más entrada; el reto de hoy cuenta como perdido.",
+    ),
+    (Key::AlreadyPlayed, "{} ya jugó el reto del {}."),
+    (Key::HintBought, "{} (-{} puntos)"),
+    (Key::FactEven, "El número secreto es par."),
+    (Key::FactOdd, "El número secreto es impar."),
+    (
+        Key::FactDivisible,
+        "El número secreto es divisible entre {}.",
+    ),
+    (
+        Key::FactNotDivisible,
+        "El número secreto no es divisible entre {}.",
+    ),
+    (
+        Key::HintNotADivisor,
+        "`{}` no es un número entero de 2 o más.",
+    ),
+    (
+        Key::HintUnknown,
+        "Las pistas son `hint parity` o `hint div N`.",
+    ),
+    (Key::Burning, "Te quemas"),
+    (Key::Warm, "Caliente"),
+    (Key::Cold, "Frío"),
+    (Key::Closer, "más cerca que la vez anterior"),
+    (Key::Farther, "más lejos que la vez anterior"),
+    (Key::AsFar, "igual de lejos que la vez anterior"),
+    (Key::Proximity, "{}."),
+    (Key::ProximityTrend, "{}, y {}."),
+    (
+        Key::CandidatesLeft,
+        "Candidatos restantes: {} a {} ({} números).",
+    ),
+    (
+        Key::BitsEarned,
+        "Ese intento aportó {} bits; la bisección garantiza {}.",
+    ),
+    (Key::ColumnGuess, "intento"),
+    (Key::ColumnResult, "resultado"),
+    (Key::ColumnLeft, "quedan"),
+    (Key::ColumnBits, "bits"),
+    (Key::ColumnIdeal, "ideal"),
+    (Key::ColumnRating, "nota"),
+    (Key::Less, "Menor"),
+    (Key::Greater, "Mayor"),
+    (Key::Equal, "Igual"),
+    (Key::Lucky, "suerte"),
+    (Key::Ideal, "ideal"),
+    (Key::Good, "bueno"),
+    (Key::Poor, "flojo"),
+    (Key::Wasted, "perdido"),
+    (Key::TotalBits, "Total: {} bits."),
+    (Key::NoGamesYet, "Aún no se ha jugado ninguna partida."),
+    (Key::StatsPlayed, "Jugadas {}, ganadas {} ({}%)"),
+    (
+        Key::StatsGuesses,
+        "Intentos para ganar: media {}, mediana {}",
+    ),
+    (Key::StatsStreak, "Racha: {} actual, {} mejor"),
+    (
+        Key::Replaying,
+        "Repetición de la semilla {}: el número secreto está entre {} y {}.",
+    ),
+    (
+        Key::ReverseIntro,
+        "Piensa un número entre {} y {}, y yo lo adivinaré.",
+    ),
+    (Key::ReverseAsk, "¿Es {}? (higher/lower/correct)"),
+    (
+        Key::ReverseFound,
+        "¡Lo tengo! Tu número es {}. Necesité {} intentos.",
+    ),
+    (Key::InputClosed, "No hay más entrada."),
+    (
+        Key::NotAnAnswer,
+        "`{}` no es una respuesta; escribe higher, lower o correct.",
+    ),
+    (Key::Higher, "mayor"),
+    (Key::Lower, "menor"),
+    (Key::Correct, "correcto"),
+    (
+        Key::CheatContradiction,
+        "¡Tramposo! Dijiste {} que {} y luego {} que {}.",
+    ),
+    (
+        Key::CheatOutsideRange,
+        "¡Tramposo! Dijiste {} que {}, pero tu número está entre {} y {}.",
+    ),
+    (Key::CowsIntro, "¡Toros y vacas! (semilla {})"),
+    (Key::CowsDigits, "El secreto tiene {} cifras distintas."),
+    (Key::CowsSolverGuess, "Digo {}."),
+    (Key::CowsScore, "{} toros, {} vacas"),
+    (
+        Key::CowsNoMoreInput,
+        "No hay más entrada; el secreto era {}.",
+    ),
+    (Key::CowsLose, "¡Has perdido! El secreto era {}."),
+    (Key::CowsSolved, "Resuelto en {} intentos."),
+    (Key::CowsWin, "¡Has ganado en {} intentos!"),
+    (
+        Key::CodeWrongLength,
+        "El código tiene {} cifras, pero escribiste {}.",
+    ),
+    (Key::CodeNotADigit, "`{}` no es una cifra."),
+    (Key::CodeOutOfRange, "{} no es una cifra del 0 al 9."),
+    (
+        Key::CodeRepeatedDigit,
+        "La cifra {} aparece más de una vez; las cifras deben ser distintas.",
+    ),
+    (Key::WordsIntro, "¡Adivina la palabra! (semilla {})"),
+    (
+        Key::WordsLength,
+        "La palabra secreta tiene {} letras. Tienes {} intentos.",
+    ),
+    (
+        Key::WordsNoMoreInput,
+        "No hay más entrada; la palabra secreta era {}.",
+    ),
+    (Key::WordsLose, "¡Has perdido! La palabra secreta era {}."),
+    (
+        Key::WordWrongLength,
+        "La palabra tiene {} letras, pero escribiste {}.",
+    ),
+    (Key::WordNotInList, "`{}` no está en la lista de palabras."),
+    (
+        Key::PartyHotSeat,
+        "¡Partida por turnos para {}! (semilla {})",
+    ),
+    (Key::PartyRace, "¡Carrera para {}! (semilla {})"),
+    (
+        Key::PartyRaceBetween,
+        "El número secreto de cada jugador está entre {} y {}.",
+    ),
+    (Key::PartyPrompt, "{}, por favor, introduce tu número."),
+    (Key::PartyGotIt, "¡{} lo ha adivinado!"),
+    (
+        Key::PartyOutOfGuesses,
+        "{} no tiene más intentos; el número secreto era {}.",
+    ),
+    (Key::PartyRanking, "Clasificación final:"),
+    (Key::PartyStanding, "{} tras {} intentos: {}"),
+    (Key::PartyWon, "ganó"),
+    (Key::PartyLost, "perdió"),
+    (Key::NoScoresYet, "Todavía no hay puntuaciones."),
+    (
+        Key::ScoresEntry,
+        "{} intentos  {} a {}  {}s  {}  puntuación {}",
+    ),
+    (Key::CouldNotRead, "No se pudo leer {}: {}"),
+    (
+        Key::ScoresUnreadable,
+        "aviso: no se pudieron leer las puntuaciones: {}",
+    ),
+    (
+        Key::VerifyMatches,
+        "{}: los {} intentos coinciden con el motor (semilla {}, {}).",
+    ),
+    (Key::GameWon, "ganada"),
+    (Key::GameLost, "perdida"),
+    (Key::GameUnfinished, "sin terminar"),
+    (
+        Key::ReplayNotAReplay,
+        "no es una repetición de guessing_game",
+    ),
+    (
+        Key::ReplayNewerVersion,
+        "el formato de repetición v{} es más nuevo de lo que este programa entiende (v{})",
+    ),
+    (Key::ReplayMissing, "la repetición no tiene línea `{}`"),
+    (Key::ReplayBadLine, "la línea {} está dañada: `{}`"),
+    (
+        Key::VerifyMismatch,
+        "el intento n.º {} ({}) se grabó como {}, pero el motor dice {}",
+    ),
+    (
+        Key::VerifyImpossible,
+        "el intento n.º {} ({}) no pudo haberse hecho",
+    ),
+    (
+        Key::SimulateIntro,
+        "Simulando {} partidas de {} a {} (semilla {})",
+    ),
+    (
+        Key::SimulateSummary,
+        "{}: media de {} intentos, máximo {}, ganadas {}/{}, puntuación media {}",
+    ),
+    (
+        Key::ServeIntro,
+        "Sirviendo partidas de {} a {} en {} (semilla {})",
+    ),
+    (Key::ServeCannotListen, "No se pudo escuchar en {}: {}"),
+    (Key::ServeStopped, "El servidor se detuvo: {}"),
+    (
+        Key::AcceptFailed,
+        "aviso: no se pudo aceptar una conexión: {}",
+    ),
+    (Key::WordsNotFound, "No se encontró {}."),
+    (Key::WordsNoneOfLength, "{} no tiene palabras de {} letras."),
+    (Key::BuiltinWordList, "La lista de palabras incorporada"),
+];
+
+const FRENCH: &[(Key, &str)] = &[
+    (Key::GuessTheNumber, "Devinez le nombre ! (graine {})"),
+    (
+        Key::SecretBetween,
+        "Le nombre secret est compris entre {} et {}.",
+    ),
+    (Key::GuessesAllowed, "Vous avez {} essais."),
+    (
+        Key::HintHelp,
+        "Tapez `hint parity` ou `hint div N` pour acheter un indice.",
+    ),
+    (Key::PleaseGuess, "Veuillez saisir votre proposition."),
+    (
+        Key::NoMoreInput,
+        "Plus d'entrée ; le nombre secret était {}.",
+    ),
+    (Key::YouGuessed, "Vous avez proposé : {}"),
+    (Key::TooSmallRange, "Trop petit ! (intervalle {} à {})"),
+    (Key::TooBigRange, "Trop grand ! (intervalle {} à {})"),
+    (Key::TooSmall, "Trop petit !"),
+    (Key::TooBig, "Trop grand !"),
+    (Key::YouWin, "Vous avez gagné !"),
+    (Key::YouLose, "Vous avez perdu ! Le nombre secret était {}."),
+    (
+        Key::EvilConsistent,
+        "Toutes les réponses étaient cohérentes avec {}.",
+    ),
+    (Key::EvilCheated, "L'hôte a triché : {}"),
+    (Key::GuessesSummary, "Essais : {} (meilleur possible : {})"),
+    (Key::HintsCost, "Les indices vous ont coûté {} points."),
+    (Key::Score, "Score : {}/{}"),
+    (Key::InputEmpty, "Veuillez taper un nombre."),
+    (Key::InputNotANumber, "`{}` n'est pas un nombre."),
+    (
+        Key::InputNegative,
+        "{} est négatif ; le nombre secret est compris entre {} et {}.",
+    ),
+    (
+        Key::InputOutOfRange,
+        "{} est hors de l'intervalle ; le nombre secret est compris entre {} et {}.",
+    ),
+    (Key::DailyPuzzle, "Défi du jour : {} !"),
+    (
+        Key::DailyPractice,
+        "Défi d'entraînement du {} ! Les entraînements ne sont pas enregistrés.",
+    ),
+    (
+        Key::DailyAbandoned,
+        "Plus d'entrée ; le défi du jour compte comme perdu.",
+    ),
+    (Key::AlreadyPlayed, "{} a déjà joué le défi du {}."),
+    (Key::HintBought, "{} (-{} points)"),
+    (Key::FactEven, "Le nombre secret est pair."),
+    (Key::FactOdd, "Le nombre secret est impair."),
+    (Key::FactDivisible, "Le nombre secret est divisible par {}."),
+    (
+        Key::FactNotDivisible,
+        "Le nombre secret n'est pas divisible par {}.",
+    ),
+    (
+        Key::HintNotADivisor,
+        "`{}` n'est pas un nombre entier supérieur ou égal à 2.",
+    ),
+    (
+        Key::HintUnknown,
+        "Les indices sont `hint parity` ou `hint div N`.",
+    ),
+    (Key::Burning, "Brûlant"),
+    (Key::Warm, "Chaud"),
+    (Key::Cold, "Froid"),
+    (Key::Closer, "plus près que la dernière fois"),
+    (Key::Farther, "plus loin que la dernière fois"),
+    (Key::AsFar, "aussi loin que la dernière fois"),
+    (Key::Proximity, "{}."),
+    (Key::ProximityTrend, "{}, et {}."),
+    (
+        Key::CandidatesLeft,
+        "Candidats restants : {} à {} ({} nombres).",
+    ),
+    (
+        Key::BitsEarned,
+        "Cette proposition a rapporté {} bits ; la dichotomie en garantit {}.",
+    ),
+    (Key::ColumnGuess, "essai"),
+    (Key::ColumnResult, "réponse"),
+    (Key::ColumnLeft, "reste"),
+    (Key::ColumnBits, "bits"),
+    (Key::ColumnIdeal, "idéal"),
+    (Key::ColumnRating, "note"),
+    (Key::Less, "Moins"),
+    (Key::Greater, "Plus"),
+    (Key::Equal, "Égal"),
+    (Key::Lucky, "chanceux"),
+    (Key::Ideal, "idéal"),
+    (Key::Good, "bon"),
+    (Key::Poor, "faible"),
+    (Key::Wasted, "gâché"),
+    (Key::TotalBits, "Total : {} bits."),
+    (Key::NoGamesYet, "Aucune partie jouée pour l'instant."),
+    (Key::StatsPlayed, "Jouées {}, gagnées {} ({} %)"),
+    (
+        Key::StatsGuesses,
+        "Essais pour gagner : moyenne {}, médiane {}",
+    ),
+    (Key::StatsStreak, "Série : {} en cours, {} record"),
+    (
+        Key::Replaying,
+        "Rediffusion de la graine {} : le nombre secret est compris entre {} et {}.",
+    ),
+    (
+        Key::ReverseIntro,
+        "Pensez à un nombre entre {} et {}, et je le devinerai.",
+    ),
+    (Key::ReverseAsk, "Est-ce {} ? (higher/lower/correct)"),
+    (
+        Key::ReverseFound,
+        "Trouvé ! Votre nombre est {}. Il m'a fallu {} essais.",
+    ),
+    (Key::InputClosed, "Plus d'entrée."),
+    (
+        Key::NotAnAnswer,
+        "`{}` n'est pas une réponse ; tapez higher, lower ou correct.",
+    ),
+    (Key::Higher, "plus grand"),
+    (Key::Lower, "plus petit"),
+    (Key::Correct, "correct"),
+    (
+        Key::CheatContradiction,
+        "Tricheur ! Vous avez dit {} que {} puis {} que {}.",
+    ),
+    (
+        Key::CheatOutsideRange,
+        "Tricheur ! Vous avez dit {} que {}, mais votre nombre est compris entre {} et {}.",
+    ),
+    (Key::CowsIntro, "Taureaux et vaches ! (graine {})"),
+    (Key::CowsDigits, "Le secret a {} chiffres différents."),
+    (Key::CowsSolverGuess, "Je propose {}."),
+    (Key::CowsScore, "{} taureaux, {} vaches"),
+    (Key::CowsNoMoreInput, "Plus d'entrée ; le secret était {}."),
+    (Key::CowsLose, "Vous avez perdu ! Le secret était {}."),
+    (Key::CowsSolved, "Résolu en {} essais."),
+    (Key::CowsWin, "Vous avez gagné en {} essais !"),
+    (
+        Key::CodeWrongLength,
+        "Le code a {} chiffres, mais vous en avez tapé {}.",
+    ),
+    (Key::CodeNotADigit, "`{}` n'est pas un chiffre."),
+    (Key::CodeOutOfRange, "{} n'est pas un chiffre de 0 à 9."),
+    (
+        Key::CodeRepeatedDigit,
+        "Le chiffre {} apparaît plusieurs fois ; les chiffres doivent être différents.",
+    ),
+    (Key::WordsIntro, "Devinez le mot ! (graine {})"),
+    (
+        Key::WordsLength,
+        "Le mot secret a {} lettres. Vous avez {} essais.",
+    ),
+    (
+        Key::WordsNoMoreInput,
+        "Plus d'entrée ; le mot secret était {}.",
+    ),
+    (Key::WordsLose, "Vous avez perdu ! Le mot secret était {}."),
+    (
+        Key::WordWrongLength,
+        "Le mot a {} lettres, mais vous en avez tapé {}.",
+    ),
+    (Key::WordNotInList, "`{}` n'est pas dans la liste de mots."),
+    (
+        Key::PartyHotSeat,
+        "Partie à tour de rôle pour {} ! (graine {})",
+    ),
+    (Key::PartyRace, "Course pour {} ! (graine {})"),
+    (
+        Key::PartyRaceBetween,
+        "Le nombre secret de chacun est compris entre {} et {}.",
+    ),
+    (Key::PartyPrompt, "{}, veuillez saisir votre proposition."),
+    (Key::PartyGotIt, "{} a trouvé !"),
+    (
+        Key::PartyOutOfGuesses,
+        "{} n'a plus d'essais ; le nombre secret était {}.",
+    ),
+    (Key::PartyRanking, "Classement final :"),
+    (Key::PartyStanding, "{} après {} essais : {}"),
+    (Key::PartyWon, "gagné"),
+    (Key::PartyLost, "perdu"),
+    (Key::NoScoresYet, "Pas encore de meilleurs scores."),
+    (Key::ScoresEntry, "{} essais  {} à {}  {}s  {}  score {}"),
+    (Key::CouldNotRead, "Impossible de lire {} : {}"),
+    (
+        Key::ScoresUnreadable,
+        "attention : impossible de lire les meilleurs scores : {}",
+    ),
+    (
+        Key::VerifyMatches,
+        "{} : les {} essais correspondent au moteur (graine {}, {}).",
+    ),
+    (Key::GameWon, "gagnée"),
+    (Key::GameLost, "perdue"),
+    (Key::GameUnfinished, "inachevée"),
+    (
+        Key::ReplayNotAReplay,
+        "ce n'est pas une rediffusion de guessing_game",
+    ),
+    (
+        Key::ReplayNewerVersion,
+        "le format de rediffusion v{} est plus récent que ce que ce programme comprend (v{})",
+    ),
+    (Key::ReplayMissing, "la rediffusion n'a pas de ligne `{}`"),
+    (Key::ReplayBadLine, "la ligne {} est abîmée : `{}`"),
+    (
+        Key::VerifyMismatch,
+        "l'essai n° {} ({}) a été enregistré comme {} mais le moteur dit {}",
+    ),
+    (
+        Key::VerifyImpossible,
+        "l'essai n° {} ({}) n'a pas pu être joué",
+    ),
+    (
+        Key::SimulateIntro,
+        "Simulation de {} parties de {} à {} (graine {})",
+    ),
+    (
+        Key::SimulateSummary,
+        "{} : {} essais en moyenne, max {}, gagnées {}/{}, score moyen {}",
+    ),
+    (
+        Key::ServeIntro,
+        "Parties de {} à {} servies sur {} (graine {})",
+    ),
+    (Key::ServeCannotListen, "Impossible d'écouter sur {} : {}"),
+    (Key::ServeStopped, "Le serveur s'est arrêté : {}"),
+    (
+        Key::AcceptFailed,
+        "attention : impossible d'accepter une connexion : {}",
+    ),
+    (Key::WordsNotFound, "{} introuvable."),
+    (Key::WordsNoneOfLength, "{} n'a aucun mot de {} lettres."),
+    (Key::BuiltinWordList, "La liste de mots intégrée"),
+];
+
+fn table(lang: Lang) -> &'static [(Key, &'static str)] {
+    match lang {
+        Lang::En => &[],
+        Lang::Es => SPANISH,
+        Lang::Fr => FRENCH,
+    }
+}
+
+fn lookup(table: &[(Key, &'static str)], key: Key) -> &'static str {
+    table
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map_or_else(|| english(key), |(_, text)| text)
+}
+
+/// The template for `key` in `lang`, falling back to English.
+pub fn text(lang: Lang, key: Key) -> &'static str {
+    lookup(table(lang), key)
+}
+
+/// The message for `key` in `lang` with each `{}` replaced by the next
+/// of `args`.
+pub fn message(lang: Lang, key: Key, args: &[&dyn fmt::Display]) -> String {
+    let mut args = args.iter();
+    let mut pieces = text(lang, key).split("{}");
+    let mut out = pieces.next().unwrap_or("").to_string();
+
+    for piece in pieces {
+        if let Some(arg) = args.next() {
+            out.push_str(&arg.to_string());
+        }
+        out.push_str(piece);
+    }
+
+    out
+}
+
+/// An [`InputError`] in `lang`.
+pub fn input_error(lang: Lang, err: &InputError) -> String {
+    match err {
+        InputError::Empty => message(lang, Key::InputEmpty, &[]),
+        InputError::NotANumber(value) => message(lang, Key::InputNotANumber, &[value]),
+        InputError::Negative { value, min, max } => {
+            message(lang, Key::InputNegative, &[value, min, max])
+        }
+        InputError::OutOfRange { value, min, max } => {
+            message(lang, Key::InputOutOfRange, &[value, min, max])
+        }
+    }
+}
+
+/// A [`HintError`] in `lang`.
+pub fn hint_error(lang: Lang, err: &HintError) -> String {
+    match err {
+        HintError::NotADivisor(n) => message(lang, Key::HintNotADivisor, &[n]),
+        HintError::Unknown => message(lang, Key::HintUnknown, &[]),
+    }
+}
+
+/// A [`Fact`] in `lang`.
+pub fn fact(lang: Lang, fact: Fact) -> String {
+    match fact {
+        Fact::Even => message(lang, Key::FactEven, &[]),
+        Fact::Odd => message(lang, Key::FactOdd, &[]),
+        Fact::DivisibleBy(n) => message(lang, Key::FactDivisible, &[&n]),
+        Fact::NotDivisibleBy(n) => message(lang, Key::FactNotDivisible, &[&n]),
+    }
+}
+
+pub fn proximity(lang: Lang, proximity: Proximity) -> &'static str {
+    text(
+        lang,
+        match proximity {
+            Proximity::Burning => Key::Burning,
+            Proximity::Warm => Key::Warm,
+            Proximity::Cold => Key::Cold,
+        },
+    )
+}
+
+pub fn trend(lang: Lang, trend: Trend) -> &'static str {
+    text(
+        lang,
+        match trend {
+            Trend::Closer => Key::Closer,
+            Trend::Farther => Key::Farther,
+            Trend::Same => Key::AsFar,
+        },
+    )
+}
+
+/// How a guess compared to the secret, as in `Less` for too small.
+pub fn ordering(lang: Lang, ordering: Ordering) -> &'static str {
+    text(
+        lang,
+        match ordering {
+            Ordering::Less => Key::Less,
+            Ordering::Greater => Key::Greater,
+            Ordering::Equal => Key::Equal,
+        },
+    )
+}
+
+pub fn rating(lang: Lang, rating: Rating) -> &'static str {
+    text(
+        lang,
+        match rating {
+            Rating::Lucky => Key::Lucky,
+            Rating::Ideal => Key::Ideal,
+            Rating::Good => Key::Good,
+            Rating::Poor => Key::Poor,
+            Rating::Wasted => Key::Wasted,
+        },
+    )
+}
+
+pub fn answer(lang: Lang, answer: Answer) -> &'static str {
+    text(
+        lang,
+        match answer {
+            Answer::Higher => Key::Higher,
+            Answer::Lower => Key::Lower,
+            Answer::Correct => Key::Correct,
+        },
+    )
+}
+
+/// A [`Cheating`] accusation in `lang`.
+pub fn cheating(lang: Lang, cheating: &Cheating) -> String {
+    match *cheating {
+        Cheating::Contradiction { first, second } => message(
+            lang,
+            Key::CheatContradiction,
+            &[
+                &answer(lang, first.1),
+                &first.0,
+                &answer(lang, second.1),
+                &second.0,
+            ],
+        ),
+        Cheating::OutsideRange {
+            guess,
+            answer: said,
+            min,
+            max,
+        } => message(
+            lang,
+            Key::CheatOutsideRange,
+            &[&answer(lang, said), &guess, &min, &max],
+        ),
+    }
+}
+
+/// A bulls-and-cows [`Score`] in `lang`.
+pub fn cows_score(lang: Lang, score: Score) -> String {
+    message(lang, Key::CowsScore, &[&score.bulls, &score.cows])
+}
+
+/// A [`CodeError`] in `lang`.
+pub fn code_error(lang: Lang, err: &CodeError) -> String {
+    match err {
+        CodeError::WrongLength { expected, found } => {
+            message(lang, Key::CodeWrongLength, &[expected, found])
+        }
+        CodeError::NotADigit(c) => message(lang, Key::CodeNotADigit, &[c]),
+        CodeError::OutOfRange(n) => message(lang, Key::CodeOutOfRange, &[n]),
+        CodeError::RepeatedDigit(d) => message(lang, Key::CodeRepeatedDigit, &[d]),
+    }
+}
+
+/// A [`WordError`] in `lang`.
+pub fn word_error(lang: Lang, err: &WordError) -> String {
+    match err {
+        WordError::WrongLength { expected, found } => {
+            message(lang, Key::WordWrongLength, &[expected, found])
+        }
+        WordError::NotInList(word) => message(lang, Key::WordNotInList, &[word]),
+    }
+}
+
+/// A [`ReplayError`] in `lang`.
+pub fn replay_error(lang: Lang, err: &ReplayError) -> String {
+    match err {
+        ReplayError::NotAReplay => message(lang, Key::ReplayNotAReplay, &[]),
+        ReplayError::UnsupportedVersion(version) => message(
+            lang,
+            Key::ReplayNewerVersion,
+            &[version, &crate::replay::VERSION],
+        ),
+        ReplayError::Missing(key) => message(lang, Key::ReplayMissing, &[key]),
+        ReplayError::BadLine { number, line } => message(lang, Key::ReplayBadLine, &[number, line]),
+    }
+}
+
+/// A [`VerifyError`] in `lang`.
+pub fn verify_error(lang: Lang, err: &VerifyError) -> String {
+    match *err {
+        VerifyError::Mismatch {
+            index,
+            guess,
+            recorded,
+            actual,
+        } => message(
+            lang,
+            Key::VerifyMismatch,
+            &[
+                &(index + 1),
+                &guess,
+                &ordering(lang, recorded),
+                &ordering(lang, actual),
+            ],
+        ),
+        VerifyError::Impossible { index, guess } => {
+            message(lang, Key::VerifyImpossible, &[&(index + 1), &guess])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_locale_has_every_key() {
+        for lang in Lang::ALL {
+            // Exactly one entry per key: none missing, none twice.
+            if lang != Lang::En {
+                assert_eq!(table(lang).len(), Key::ALL.len(), "{}", lang);
+            }
+
+            for &key in Key::ALL {
+                let found = lang == Lang::En || table(lang).iter().any(|(k, _)| *k == key);
+                assert!(found, "{} has no translation for {:?}", lang, key);
+
+                let placeholders = text(lang, key).matches("{}").count();
+                assert_eq!(
+                    placeholders,
+                    english(key).matches("{}").count(),
+                    "{} has the wrong placeholders for {:?}",
+                    lang,
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn missing_translations_fall_back_to_english() {
+        assert_eq!(lookup(&[], Key::YouWin), "You win!");
+        assert_eq!(
+            lookup(&[(Key::YouWin, "¡Has ganado!")], Key::TooBig),
+            "Too big!"
+        );
+    }
+
+    #[test]
+    fn fills_placeholders_in_order() {
+        assert_eq!(
+            message(Lang::Fr, Key::SecretBetween, &[&1, &100]),
+            "Le nombre secret est compris entre 1 et 100."
+        );
+        assert_eq!(message(Lang::Es, Key::YouWin, &[]), "¡Has ganado!");
+        assert_eq!(
+            input_error(Lang::En, &InputError::NotANumber("abc".to_string())),
+            InputError::NotANumber("abc".to_string()).to_string()
+        );
+    }
+
+    #[test]
+    fn english_matches_the_library_messages() {
+        let accused = Cheating::Contradiction {
+            first: (5, Answer::Higher),
+            second: (3, Answer::Lower),
+        };
+        assert_eq!(cheating(Lang::En, &accused), accused.to_string());
+
+        let err = CodeError::WrongLength {
+            expected: 4,
+            found: 3,
+        };
+        assert_eq!(code_error(Lang::En, &err), err.to_string());
+        assert_eq!(
+            fact(Lang::En, Fact::NotDivisibleBy(3)),
+            Fact::NotDivisibleBy(3).to_string()
+        );
+        assert_eq!(
+            hint_error(Lang::En, &HintError::Unknown),
+            HintError::Unknown.to_string()
+        );
+        for err in [
+            ReplayError::NotAReplay,
+            ReplayError::UnsupportedVersion(9),
+            ReplayError::Missing("seed"),
+            ReplayError::BadLine {
+                number: 4,
+                line: "guess x".to_string(),
+            },
+        ] {
+            assert_eq!(replay_error(Lang::En, &err), err.to_string());
+        }
+        for err in [
+            VerifyError::Mismatch {
+                index: 0,
+                guess: 10,
+                recorded: Ordering::Less,
+                actual: Ordering::Greater,
+            },
+            VerifyError::Impossible { index: 2, guess: 7 },
+        ] {
+            assert_eq!(verify_error(Lang::En, &err), err.to_string());
+        }
+        assert_eq!(
+            cheating(Lang::Fr, &accused),
+            "Tricheur ! Vous avez dit plus grand que 5 puis plus petit que 3."
+        );
+    }
+
+    #[test]
+    fn reads_locales() {
+        assert_eq!(Lang::from_locale("fr_FR.UTF-8"), Some(Lang::Fr));
+        assert_eq!(Lang::from_locale("es"), Some(Lang::Es));
+        assert_eq!(Lang::from_locale("en_GB"), Some(Lang::En));
+        assert_eq!(Lang::from_locale("de_DE.UTF-8"), None);
+        assert_eq!(Lang::from_locale("C"), None);
+        assert!("FR".parse::<Lang>().is_ok());
+        assert!("xx".parse::<Lang>().is_err());
+    }
+}
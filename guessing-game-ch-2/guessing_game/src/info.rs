@@ -0,0 +1,171 @@
+//! How much each guess taught the player, in bits.
+//!
+//! A guess that cuts `before` candidates down to `after` earns
+//! `log2(before / after)` bits. Guessing the midpoint of the remaining
+//! interval, as binary search does, always leaves at most `before / 2`
+//! candidates (rounding down), so it is guaranteed about one bit. That
+//! guarantee is the yardstick every guess is measured against.
+
+use crate::interval::Interval;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// Rounding slack when comparing bits, which are floating point.
+const EPSILON: f64 = 1e-9;
+
+/// How a guess compares with an ideal bisection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rating {
+    /// Earned more than bisection guarantees, thanks to the answer.
+    Lucky,
+    /// Earned what bisection guarantees.
+    Ideal,
+    /// Earned at least half of it.
+    Good,
+    /// Earned something, but less than half.
+    Poor,
+    /// Ruled nothing out, e.g. a guess outside the remaining interval.
+    Wasted,
+}
+
+impl fmt::Display for Rating {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Rating::Lucky => "lucky",
+            Rating::Ideal => "ideal",
+            Rating::Good => "good",
+            Rating::Poor => "poor",
+            Rating::Wasted => "wasted",
+        })
+    }
+}
+
+/// What one guess did to the candidate interval.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GuessInfo {
+    pub guess: i64,
+    /// `guess.cmp(&secret)`.
+    pub ordering: Ordering,
+    pub before: Interval,
+    pub after: Interval,
+}
+
+impl GuessInfo {
+    /// Information earned by this guess.
+    pub fn bits(&self) -> f64 {
+        bits(self.before.size(), self.after.size())
+    }
+
+    /// Information a midpoint guess is guaranteed from the same interval.
+    pub fn ideal_bits(&self) -> f64 {
+        let before = self.before.size();
+        bits(before, (before / 2).max(1))
+    }
+
+    pub fn rating(&self) -> Rating {
+        let (bits, ideal) = (self.bits(), self.ideal_bits());
+
+        if bits > ideal + EPSILON {
+            Rating::Lucky
+        } else if bits >= ideal - EPSILON {
+            Rating::Ideal
+        } else if bits >= ideal / 2.0 - EPSILON {
+            Rating::Good
+        } else if bits > EPSILON {
+            Rating::Poor
+        } else {
+            Rating::Wasted
+        }
+    }
+}
+
+fn bits(before: u128, after: u128) -> f64 {
+    if before == 0 || after == 0 {
+        return 0.0;
+    }
+    (before as f64 / after as f64).log2()
+}
+
+/// Replays `transcript` from the full `range`, one entry per guess.
+pub fn analyze(range: &RangeInclusive<i64>, transcript: &[(i64, Ordering)]) -> Vec<GuessInfo> {
+    let mut interval = Interval::new(range);
+
+    transcript
+        .iter()
+        .map(|&(guess, ordering)| {
+            let before = interval;
+            interval.narrow(guess, ordering);
+            GuessInfo {
+                guess,
+                ordering,
+                before,
+                after: interval,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn bisection_earns_one_bit_per_guess() {
+        let infos = analyze(&(1..=8), &[(4, Ordering::Less), (6, Ordering::Less)]);
+
+        assert_eq!(infos[0].after.size(), 4);
+        assert!(close(infos[0].bits(), 1.0));
+        assert!(close(infos[0].ideal_bits(), 1.0));
+        assert_eq!(infos[0].rating(), Rating::Ideal);
+        assert_eq!(
+            (infos[1].after.low(), infos[1].after.high()),
+            (Some(7), Some(8))
+        );
+        assert_eq!(infos[1].rating(), Rating::Ideal);
+    }
+
+    #[test]
+    fn rates_lopsided_guesses() {
+        let infos = analyze(
+            &(1..=100),
+            &[
+                (90, Ordering::Greater),
+                (10, Ordering::Greater),
+                (20, Ordering::Greater),
+                (5, Ordering::Equal),
+            ],
+        );
+        let ratings: Vec<Rating> = infos.iter().map(GuessInfo::rating).collect();
+
+        // 100 -> 89 candidates, then 89 -> 9, then nothing ruled out, then
+        // an early win.
+        assert_eq!(
+            ratings,
+            [Rating::Poor, Rating::Lucky, Rating::Wasted, Rating::Lucky]
+        );
+        assert!(close(infos[3].bits(), 9f64.log2()));
+    }
+
+    #[test]
+    fn good_is_at_least_half_of_ideal() {
+        // 64 -> 40 candidates earns about 0.68 of the ideal 1 bit.
+        let infos = analyze(&(1..=64), &[(24, Ordering::Less)]);
+
+        assert_eq!(infos[0].after.size(), 40);
+        assert_eq!(infos[0].rating(), Rating::Good);
+    }
+
+    #[test]
+    fn last_candidate_needs_no_information() {
+        let infos = analyze(&(7..=7), &[(7, Ordering::Equal)]);
+
+        assert!(close(infos[0].bits(), 0.0));
+        assert!(close(infos[0].ideal_bits(), 0.0));
+        assert_eq!(infos[0].rating(), Rating::Ideal);
+    }
+}
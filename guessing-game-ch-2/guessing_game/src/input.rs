@@ -0,0 +1,163 @@
+use std::error::Error;
+use std::fmt;
+use std::num::IntErrorKind;
+use std::ops::RangeInclusive;
+
+/// Why a line typed by the player is not a usable guess.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputError {
+    Empty,
+    NotANumber(String),
+    Negative { value: String, min: i64, max: i64 },
+    OutOfRange { value: String, min: i64, max: i64 },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Empty => write!(f, "Please type a number."),
+            InputError::NotANumber(value) => write!(f, "`{}` is not a number.", value),
+            InputError::Negative { value, min, max } => write!(
+                f,
+                "{} is negative; the secret is between {} and {}.",
+                value, min, max
+            ),
+            InputError::OutOfRange { value, min, max } => write!(
+                f,
+                "{} is out of range; the secret is between {} and {}.",
+                value, min, max
+            ),
+        }
+    }
+}
+
+impl Error for InputError {}
+
+/// Parses one line of player input into a guess inside `range`.
+///
+/// Surrounding whitespace is ignored. Negative numbers get their own error
+/// when the range has none, since that is usually a typo rather than a
+/// strategy.
+pub fn parse_guess(line: &str, range: &RangeInclusive<i64>) -> Result<i64, InputError> {
+    let text = line.trim();
+    let (min, max) = (*range.start(), *range.end());
+    let negative = || InputError::Negative {
+        value: text.to_string(),
+        min,
+        max,
+    };
+    let out_of_range = || InputError::OutOfRange {
+        value: text.to_string(),
+        min,
+        max,
+    };
+
+    if text.is_empty() {
+        return Err(InputError::Empty);
+    }
+
+    let value: i64 = match text.parse() {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(match err.kind() {
+                IntErrorKind::NegOverflow if min >= 0 => negative(),
+                IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => out_of_range(),
+                _ => InputError::NotANumber(text.to_string()),
+            })
+        }
+    };
+
+    in_range(value, text, range)
+}
+
+/// Checks a guess that is already a number, such as one sent over the
+/// network, with the same errors as [`parse_guess`].
+pub fn check_guess(value: i64, range: &RangeInclusive<i64>) -> Result<i64, InputError> {
+    in_range(value, &value.to_string(), range)
+}
+
+/// `value` if it is in `range`; `text` is how the player wrote it.
+fn in_range(value: i64, text: &str, range: &RangeInclusive<i64>) -> Result<i64, InputError> {
+    let (min, max) = (*range.start(), *range.end());
+
+    if value < 0 && min >= 0 {
+        Err(InputError::Negative {
+            value: text.to_string(),
+            min,
+            max,
+        })
+    } else if !range.contains(&value) {
+        Err(InputError::OutOfRange {
+            value: text.to_string(),
+            min,
+            max,
+        })
+    } else {
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_numbers_in_range() {
+        assert_eq!(parse_guess(" 42\n", &(1..=100)), Ok(42));
+        assert_eq!(parse_guess("-7", &(-10..=10)), Ok(-7));
+    }
+
+    #[test]
+    fn tells_errors_apart() {
+        let range = 1..=100;
+
+        assert_eq!(parse_guess("  \n", &range), Err(InputError::Empty));
+        assert_eq!(
+            parse_guess("abc", &range),
+            Err(InputError::NotANumber("abc".to_string()))
+        );
+        assert!(matches!(
+            parse_guess("-3", &range),
+            Err(InputError::Negative { .. })
+        ));
+        assert!(matches!(
+            parse_guess("101", &range),
+            Err(InputError::OutOfRange { .. })
+        ));
+        assert!(matches!(
+            parse_guess("-11", &(-10..=10)),
+            Err(InputError::OutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn checks_numbers_that_need_no_parsing() {
+        assert_eq!(check_guess(42, &(1..=100)), Ok(42));
+        assert_eq!(
+            check_guess(-3, &(1..=100)),
+            Err(InputError::Negative {
+                value: "-3".to_string(),
+                min: 1,
+                max: 100
+            })
+        );
+        assert!(matches!(
+            check_guess(i64::MAX, &(1..=100)),
+            Err(InputError::OutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn huge_numbers_are_out_of_range_not_garbage() {
+        let range = 1..=100;
+
+        assert!(matches!(
+            parse_guess("99999999999999999999", &range),
+            Err(InputError::OutOfRange { .. })
+        ));
+        assert!(matches!(
+            parse_guess("-99999999999999999999", &range),
+            Err(InputError::Negative { .. })
+        ));
+    }
+}
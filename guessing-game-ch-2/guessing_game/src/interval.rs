@@ -0,0 +1,113 @@
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+
+/// The numbers that are still possible given the feedback so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interval {
+    bounds: Option<(i64, i64)>,
+}
+
+impl Interval {
+    pub fn new(range: &RangeInclusive<i64>) -> Interval {
+        if range.is_empty() {
+            Interval { bounds: None }
+        } else {
+            Interval {
+                bounds: Some((*range.start(), *range.end())),
+            }
+        }
+    }
+
+    pub fn low(&self) -> Option<i64> {
+        self.bounds.map(|(low, _)| low)
+    }
+
+    pub fn high(&self) -> Option<i64> {
+        self.bounds.map(|(_, high)| high)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bounds.is_none()
+    }
+
+    pub fn contains(&self, n: i64) -> bool {
+        matches!(self.bounds, Some((low, high)) if low <= n && n <= high)
+    }
+
+    /// How many candidates are left.
+    pub fn size(&self) -> u128 {
+        match self.bounds {
+            Some((low, high)) => (high as i128 - low as i128 + 1) as u128,
+            None => 0,
+        }
+    }
+
+    /// The candidate in the middle, rounding down; `None` once empty.
+    pub fn midpoint(&self) -> Option<i64> {
+        self.bounds
+            .map(|(low, high)| ((low as i128 + high as i128).div_euclid(2)) as i64)
+    }
+
+    /// The candidates still possible after learning that `guess` compared
+    /// to the secret as `ordering`, i.e. `guess.cmp(&secret)`.
+    pub fn narrowed(&self, guess: i64, ordering: Ordering) -> Interval {
+        let bounds = self.bounds.and_then(|(low, high)| match ordering {
+            Ordering::Less => Some((low.max(guess.checked_add(1)?), high)),
+            Ordering::Greater => Some((low, high.min(guess.checked_sub(1)?))),
+            Ordering::Equal if low <= guess && guess <= high => Some((guess, guess)),
+            Ordering::Equal => None,
+        });
+
+        Interval {
+            bounds: bounds.filter(|(low, high)| low <= high),
+        }
+    }
+
+    pub fn narrow(&mut self, guess: i64, ordering: Ordering) {
+        *self = self.narrowed(guess, ordering);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn narrows_from_feedback() {
+        let mut interval = Interval::new(&(1..=100));
+
+        interval.narrow(50, Ordering::Less);
+        assert_eq!((interval.low(), interval.high()), (Some(51), Some(100)));
+        interval.narrow(80, Ordering::Greater);
+        assert_eq!((interval.low(), interval.high()), (Some(51), Some(79)));
+        assert_eq!(interval.size(), 29);
+        assert_eq!(interval.midpoint(), Some(65));
+
+        interval.narrow(10, Ordering::Less);
+        assert_eq!(interval.size(), 29);
+
+        interval.narrow(60, Ordering::Equal);
+        assert_eq!(interval.size(), 1);
+    }
+
+    #[test]
+    fn contradictions_empty_it() {
+        let mut interval = Interval::new(&(1..=100));
+
+        interval.narrow(50, Ordering::Less);
+        interval.narrow(40, Ordering::Greater);
+
+        assert!(interval.is_empty());
+        assert_eq!(interval.midpoint(), None);
+    }
+
+    #[test]
+    fn survives_i64_extremes() {
+        let full = Interval::new(&(i64::MIN..=i64::MAX));
+
+        assert_eq!(full.size(), 1 << 64);
+        assert_eq!(full.midpoint(), Some(-1));
+        assert!(full.narrowed(i64::MAX, Ordering::Less).is_empty());
+        assert!(full.narrowed(i64::MIN, Ordering::Greater).is_empty());
+    }
+}
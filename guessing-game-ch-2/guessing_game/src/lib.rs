@@ -0,0 +1,409 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+
+pub mod bulls_cows;
+pub mod config;
+pub mod daily;
+pub mod date;
+pub mod evil;
+pub mod highscore;
+pub mod hint;
+pub mod i18n;
+pub mod info;
+pub mod input;
+pub mod interval;
+pub mod multiplayer;
+pub mod net;
+pub mod protocol;
+pub mod replay;
+pub mod reverse;
+pub mod score;
+pub mod stats;
+pub mod storage;
+pub mod strategy;
+pub mod transcript;
+pub mod words;
+
+pub use config::{Config, Difficulty};
+pub use input::{check_guess, parse_guess, InputError};
+
+use hint::{Fact, PaidHint};
+use interval::Interval;
+
+/// What the game says back about a single guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    TooSmall,
+    TooBig,
+    Win,
+}
+
+impl Outcome {
+    /// How the guess compared to the secret.
+    pub fn ordering(self) -> Ordering {
+        match self {
+            Outcome::TooSmall => Ordering::Less,
+            Outcome::TooBig => Ordering::Greater,
+            Outcome::Win => Ordering::Equal,
+        }
+    }
+}
+
+impl From<Ordering> for Outcome {
+    fn from(ordering: Ordering) -> Outcome {
+        match ordering {
+            Ordering::Less => Outcome::TooSmall,
+            Ordering::Greater => Outcome::TooBig,
+            Ordering::Equal => Outcome::Win,
+        }
+    }
+}
+
+/// Builds the random number generator used to pick secrets.
+///
+/// The same seed always produces the same sequence of secrets, so a session
+/// can be replayed exactly.
+pub fn seeded_rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+#[derive(Debug, Clone)]
+enum Secret {
+    Fixed(i64),
+    /// Not chosen yet: any number left in the interval fits the answers.
+    Undecided(Interval),
+}
+
+/// A single round of the guessing game.
+///
+/// `Game` owns the secret number, the range it was drawn from and every
+/// guess made so far. It never reads or prints anything, so any front end
+/// can drive it.
+#[derive(Debug, Clone)]
+pub struct Game {
+    secret: Secret,
+    range: RangeInclusive<i64>,
+    max_attempts: Option<u32>,
+    history: Vec<i64>,
+    replies: Vec<Outcome>,
+    hint_penalty: u32,
+}
+
+impl Game {
+    /// Starts a game with a secret drawn from `range` using `rng`.
+    ///
+    /// Pass a seeded generator (see [`seeded_rng`]) to get the same secret
+    /// every time.
+    pub fn new<R: Rng + ?Sized>(range: RangeInclusive<i64>, rng: &mut R) -> Game {
+        let secret = rng.gen_range(range.clone());
+        Game::with_secret(range, secret)
+    }
+
+    /// Starts a game with a known secret.
+    ///
+    /// Panics if `secret` is not inside `range`.
+    pub fn with_secret(range: RangeInclusive<i64>, secret: i64) -> Game {
+        if !range.contains(&secret) {
+            panic!(
+                "Secret {} is outside the range {}..={}.",
+                secret,
+                range.start(),
+                range.end()
+            );
+        }
+
+        Game {
+            secret: Secret::Fixed(secret),
+            range,
+            max_attempts: None,
+            history: Vec::new(),
+            replies: Vec::new(),
+            hint_penalty: 0,
+        }
+    }
+
+    /// Starts a game against the adversarial host described in [`evil`].
+    ///
+    /// Panics if `range` is empty.
+    pub fn evil(range: RangeInclusive<i64>) -> Game {
+        if range.is_empty() {
+            panic!("Cannot play in an empty range.");
+        }
+
+        Game {
+            secret: Secret::Undecided(Interval::new(&range)),
+            range,
+            max_attempts: None,
+            history: Vec::new(),
+            replies: Vec::new(),
+            hint_penalty: 0,
+        }
+    }
+
+    /// Starts a game using the range, attempt budget and host of `config`.
+    pub fn from_config<R: Rng + ?Sized>(config: &Config, rng: &mut R) -> Game {
+        let game = if config.evil {
+            Game::evil(config.range.clone())
+        } else {
+            Game::new(config.range.clone(), rng)
+        };
+        game.with_max_attempts(config.max_attempts)
+    }
+
+    /// Limits how many guesses the player gets; `None` means no limit.
+    pub fn with_max_attempts(mut self, max_attempts: Option<u32>) -> Game {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Checks a guess against the secret.
+    ///
+    /// Panics if the game is already over.
+    pub fn guess(&mut self, guess: i64) -> Outcome {
+        if self.is_over() {
+            panic!("Cannot guess after the game is over.");
+        }
+
+        let ordering = match &mut self.secret {
+            Secret::Fixed(secret) => guess.cmp(secret),
+            Secret::Undecided(candidates) => {
+                let ordering = evil::evil_answer(candidates, guess);
+                candidates.narrow(guess, ordering);
+                ordering
+            }
+        };
+
+        let outcome = Outcome::from(ordering);
+        self.history.push(guess);
+        self.replies.push(outcome);
+        outcome
+    }
+
+    /// The secret number.
+    ///
+    /// An adversarial game has no secret until the end, so this reveals a
+    /// number that fits every answer given so far.
+    pub fn secret(&self) -> i64 {
+        match &self.secret {
+            Secret::Fixed(secret) => *secret,
+            Secret::Undecided(candidates) => candidates.low().expect("no candidates left"),
+        }
+    }
+
+    pub fn is_evil(&self) -> bool {
+        matches!(self.secret, Secret::Undecided(_))
+    }
+
+    /// Every guess paired with the answer it got, as `guess.cmp(&secret)`.
+    pub fn transcript(&self) -> Vec<(i64, Ordering)> {
+        self.history
+            .iter()
+            .zip(&self.replies)
+            .map(|(&guess, outcome)| (guess, outcome.ordering()))
+            .collect()
+    }
+
+    pub fn range(&self) -> &RangeInclusive<i64> {
+        &self.range
+    }
+
+    pub fn history(&self) -> &[i64] {
+        &self.history
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.history.len() as u32
+    }
+
+    pub fn max_attempts(&self) -> Option<u32> {
+        self.max_attempts
+    }
+
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts()))
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.replies.last() == Some(&Outcome::Win)
+    }
+
+    pub fn is_lost(&self) -> bool {
+        !self.is_won() && self.attempts_left() == Some(0)
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.is_won() || self.is_lost()
+    }
+
+    /// The fewest guesses that always find the secret in this range.
+    pub fn optimal_guesses(&self) -> u32 {
+        score::optimal_guesses(&self.range)
+    }
+
+    /// The score so far; zero until the game is won.
+    pub fn score(&self) -> u32 {
+        score::score(self.is_won(), self.attempts(), self.optimal_guesses())
+            .saturating_sub(self.hint_penalty)
+    }
+
+    /// Reveals a fact about the secret in exchange for score points.
+    ///
+    /// Returns `None` against the evil host, which has no secret to reveal.
+    pub fn buy_hint(&mut self, hint: PaidHint) -> Option<Fact> {
+        match self.secret {
+            Secret::Fixed(secret) => {
+                self.hint_penalty += hint.cost();
+                Some(hint.reveal(secret))
+            }
+            Secret::Undecided(_) => None,
+        }
+    }
+
+    /// The score points spent on hints so far.
+    pub fn hint_penalty(&self) -> u32 {
+        self.hint_penalty
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_direction_until_win() {
+        let mut game = Game::with_secret(1..=100, 42);
+
+        assert_eq!(game.guess(10), Outcome::TooSmall);
+        assert_eq!(game.guess(90), Outcome::TooBig);
+        assert!(!game.is_won());
+        assert_eq!(game.guess(42), Outcome::Win);
+        assert!(game.is_won());
+        assert!(game.is_over());
+        assert_eq!(game.history(), &[10, 90, 42]);
+        assert_eq!(game.attempts(), 3);
+    }
+
+    #[test]
+    fn random_secret_stays_in_range() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let game = Game::new(-10..=10, &mut rng);
+            assert!(game.range().contains(&game.secret()));
+        }
+    }
+
+    #[test]
+    fn full_i64_range() {
+        let game = Game::new(i64::MIN..=i64::MAX, &mut rand::thread_rng());
+        assert!(game.range().contains(&game.secret()));
+    }
+
+    #[test]
+    fn same_seed_same_secrets() {
+        let secrets = |seed| {
+            let mut rng = seeded_rng(seed);
+            (0..20)
+                .map(|_| Game::new(1..=1_000_000, &mut rng).secret())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(secrets(7), secrets(7));
+        assert_ne!(secrets(7), secrets(8));
+    }
+
+    #[test]
+    fn running_out_of_attempts_loses() {
+        let mut game = Game::with_secret(1..=100, 42).with_max_attempts(Some(2));
+
+        game.guess(1);
+        assert_eq!(game.attempts_left(), Some(1));
+        assert!(!game.is_over());
+        game.guess(2);
+        assert!(game.is_lost());
+        assert!(game.is_over());
+        assert_eq!(game.score(), 0);
+    }
+
+    #[test]
+    fn score_counts_guesses_against_optimal() {
+        let mut game = Game::with_secret(1..=100, 42);
+        for guess in 1..=13 {
+            game.guess(guess);
+        }
+        game.guess(42);
+
+        assert_eq!(game.optimal_guesses(), 7);
+        assert_eq!(game.score(), 50);
+    }
+
+    #[test]
+    fn winning_on_last_attempt_is_a_win() {
+        let mut game = Game::with_secret(1..=100, 42).with_max_attempts(Some(1));
+
+        assert_eq!(game.guess(42), Outcome::Win);
+        assert!(game.is_won());
+        assert!(!game.is_lost());
+    }
+
+    #[test]
+    #[should_panic(expected = "game is over")]
+    fn guessing_after_game_over() {
+        let mut game = Game::with_secret(1..=100, 42);
+        game.guess(42);
+        game.guess(42);
+    }
+
+    #[test]
+    fn paid_hints_cost_score() {
+        let mut game = Game::with_secret(1..=100, 42);
+
+        assert_eq!(game.buy_hint(PaidHint::Parity), Some(Fact::Even));
+        game.guess(42);
+
+        assert_eq!(game.hint_penalty(), 15);
+        assert_eq!(game.score(), 85);
+        assert_eq!(Game::evil(1..=100).buy_hint(PaidHint::Parity), None);
+    }
+
+    #[test]
+    fn evil_host_forces_a_full_bisection() {
+        let mut game = Game::evil(1..=100);
+        let mut candidates = Interval::new(game.range());
+
+        while !game.is_over() {
+            let guess = candidates.midpoint().unwrap();
+            let outcome = game.guess(guess);
+            candidates.narrow(guess, outcome.ordering());
+        }
+
+        assert!(game.is_won());
+        assert_eq!(game.attempts(), 7);
+        assert_eq!(
+            evil::verify_transcript(game.range(), &game.transcript(), game.secret()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn evil_host_reveals_a_consistent_secret_after_a_loss() {
+        let mut game = Game::evil(1..=100).with_max_attempts(Some(3));
+        for guess in [10, 90, 50] {
+            assert_ne!(game.guess(guess), Outcome::Win);
+        }
+
+        assert!(game.is_lost());
+        assert_eq!(
+            evil::verify_transcript(game.range(), &game.transcript(), game.secret()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "outside the range")]
+    fn secret_outside_range() {
+        Game::with_secret(1..=100, 101);
+    }
+}
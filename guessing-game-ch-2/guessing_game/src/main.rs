@@ -1,35 +1,1436 @@
-use std::io;
-use rand::Rng;
+use guessing_game::bulls_cows::{self, Code, CowsGame, Solver};
+use guessing_game::daily::{self, DailyLog, DailyResult};
+use guessing_game::date::Date;
+use guessing_game::evil;
+use guessing_game::highscore::{Entry, HighScores};
+use guessing_game::hint::{self, PaidHint};
+use guessing_game::i18n::{self, Key, Lang};
+use guessing_game::info::{self, GuessInfo};
+use guessing_game::multiplayer::{Match, Mode};
+use guessing_game::net::{self, Client};
+use guessing_game::protocol::{Command, Reply};
+use guessing_game::replay::{self, Replay};
+use guessing_game::reverse::{Answer, Reverser};
+use guessing_game::stats::Stats;
+use guessing_game::strategy::{self, STRATEGY_NAMES};
+use guessing_game::transcript::GuessRecord;
+use guessing_game::words::{self, WordGame, WordList};
+use guessing_game::{parse_guess, score, seeded_rng, Config, Game, Outcome};
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Exit code used when stdin closes before the game is over.
+const EXIT_EOF: i32 = 3;
+
+/// Exit code used when the player contradicts their own answers.
+const EXIT_CHEATING: i32 = 4;
+
+/// Exit code used when a replay does not match what the engine does.
+const EXIT_MISMATCH: i32 = 5;
+
+const USAGE: &str = "\
+usage: guessing_game [--difficulty easy|normal|hard] [--min N] [--max N]
+                     [--max-guesses N] [--seed N] [--name NAME] [--evil]
+                     [--script FILE|-] [--json] [--hints] [--info] [--record FILE]
+                     [--lang en|es|fr]
+       guessing_game replay FILE [--speed N] [--lang en|es|fr]
+       guessing_game verify FILE [--lang en|es|fr]
+       guessing_game daily [--salt TEXT] [--date YYYY-MM-DD] [--name NAME]
+                     [--difficulty ...] [--min N] [--max N] [--max-guesses N]
+                     [--lang en|es|fr]
+       guessing_game scores [--top N] [--lang en|es|fr]
+       guessing_game stats [--merge FILE] [--lang en|es|fr]
+       guessing_game simulate [--games N] [--strategy binary|random|linear]
+                     [--difficulty ...] [--min N] [--max N] [--max-guesses N] [--seed N] [--evil]
+                     [--lang en|es|fr]
+       guessing_game reverse [--difficulty ...] [--min N] [--max N] [--lang en|es|fr]
+       guessing_game cows [--digits N] [--max-guesses N] [--seed N] [--solve]
+                     [--lang en|es|fr]
+       guessing_game words [--words FILE] [--length N] [--max-guesses N] [--seed N]
+                     [--name NAME] [--script FILE|-] [--lang en|es|fr]
+       guessing_game party --players NAME,NAME... [--mode hotseat|race]
+                     [--difficulty ...] [--min N] [--max N] [--max-guesses N] [--seed N]
+                     [--lang en|es|fr]
+       guessing_game serve [--addr HOST:PORT] [--difficulty ...] [--min N] [--max N]
+                     [--max-guesses N] [--seed N] [--evil] [--lang en|es|fr]
+       guessing_game client [--addr HOST:PORT]";
+
+const DEFAULT_ADDR: &str = "127.0.0.1:7878";
+
+/// The slowest and fastest `replay --speed`, besides 0 for no waiting.
+const MIN_REPLAY_SPEED: f64 = 0.01;
+const MAX_REPLAY_SPEED: f64 = 1_000_000.0;
 
 fn main() {
-    println!("Guess the number!");
+    let mut args = env::args().skip(1).peekable();
+
+    match args.peek().map(String::as_str) {
+        Some("scores") => {
+            args.next();
+            scores(args)
+        }
+        Some("daily") => {
+            args.next();
+            daily_game(args)
+        }
+        Some("replay") => {
+            args.next();
+            replay(args)
+        }
+        Some("verify") => {
+            args.next();
+            verify(args)
+        }
+        Some("stats") => {
+            args.next();
+            stats(args)
+        }
+        Some("simulate") => {
+            args.next();
+            simulate(args)
+        }
+        Some("reverse") => {
+            args.next();
+            reverse(args)
+        }
+        Some("cows") => {
+            args.next();
+            cows(args)
+        }
+        Some("words") => {
+            args.next();
+            word_game(args)
+        }
+        Some("party") => {
+            args.next();
+            party(args)
+        }
+        Some("serve") => {
+            args.next();
+            serve(args)
+        }
+        Some("client") => {
+            args.next();
+            client(args)
+        }
+        _ => play(args),
+    }
+}
 
-    let secret_number: u32 = rand::thread_rng().gen_range(1..101);
+fn usage_error(err: &str) -> ! {
+    eprintln!("error: {}", err);
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
+
+/// Exits with a usage error if `config` has a flag, like `--json`, that
+/// `command` has no use for and would otherwise silently ignore.
+fn reject_flags(config: &Config, command: &str, supported: &[&str]) {
+    let optional = config.optional_flags();
+    if let Some(flag) = optional.iter().find(|flag| !supported.contains(flag)) {
+        usage_error(&format!("`{}` does not take `{}`", command, flag));
+    }
+}
+
+/// Parses the value of `--lang` for subcommands that take no [`Config`].
+fn lang_arg(value: Option<String>) -> Lang {
+    value
+        .unwrap_or_default()
+        .parse()
+        .unwrap_or_else(|err: String| usage_error(&err))
+}
+
+/// Reads one line; `None` means the input has been closed.
+fn read_line(input: &mut dyn BufRead) -> Option<String> {
+    let mut line = String::new();
+
+    let read = input.read_line(&mut line).unwrap_or_else(|err| {
+        eprintln!("Failed to read line: {}", err);
+        process::exit(1);
+    });
+
+    (read > 0).then_some(line)
+}
+
+fn play(args: impl Iterator<Item = String>) {
+    let config = Config::from_args(args).unwrap_or_else(|err| usage_error(&err));
+
+    let lang = config.lang.unwrap_or_else(Lang::from_env);
+    let seed = config.seed.unwrap_or_else(rand::random);
+    let mut rng = seeded_rng(seed);
+    let mut game = Game::from_config(&config, &mut rng);
+    let (min, max) = (*game.range().start(), *game.range().end());
+
+    // `--json` leaves stdout to the records; `--script` just drops prompts.
+    let text = !config.json;
+    let prompt = text && config.script.is_none();
+    let mut input = open_input(config.script.as_deref());
+
+    if text {
+        println!("{}", i18n::message(lang, Key::GuessTheNumber, &[&seed]));
+        println!("{}", i18n::message(lang, Key::SecretBetween, &[&min, &max]));
+        if let Some(max_attempts) = game.max_attempts() {
+            println!(
+                "{}",
+                i18n::message(lang, Key::GuessesAllowed, &[&max_attempts])
+            );
+        }
+        if config.hints {
+            println!("{}", i18n::text(lang, Key::HintHelp));
+        }
+    }
+
+    let started = Instant::now();
+    let mut replay = Replay::new(&config, seed);
 
     loop {
-        println!("Please input your guess.");
+        if prompt {
+            println!("{}", i18n::text(lang, Key::PleaseGuess));
+        }
 
-        let mut guess = String::new();
+        let Some(guess) = read_line(input.as_mut()) else {
+            eprintln!(
+                "{}",
+                i18n::message(lang, Key::NoMoreInput, &[&game.secret()])
+            );
+            save_replay(&config, &replay);
+            process::exit(EXIT_EOF);
+        };
 
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read line");
+        if let Some(request) = guess.trim().strip_prefix("hint").filter(|_| config.hints) {
+            let reply = match request.parse::<PaidHint>() {
+                Ok(hint) => game.buy_hint(hint).map(|fact| {
+                    let fact = i18n::fact(lang, fact);
+                    i18n::message(lang, Key::HintBought, &[&fact, &hint.cost()])
+                }),
+                Err(err) => Some(i18n::hint_error(lang, &err)),
+            };
+            // Like input errors, hints stay off stdout so `--json` output
+            // is nothing but records.
+            match reply {
+                Some(reply) if text => println!("{}", reply),
+                Some(reply) => eprintln!("{}", reply),
+                None => {}
+            }
+            continue;
+        }
 
-        let guess: u32 = match guess.trim().parse() {
+        let guess = match parse_guess(&guess, game.range()) {
             Ok(num) => num,
-            Err(_) => continue,
+            Err(err) if text => {
+                println!("{}", i18n::input_error(lang, &err));
+                continue;
+            }
+            Err(err) => {
+                eprintln!("{}", i18n::input_error(lang, &err));
+                continue;
+            }
         };
-        
-        println!("You guessed: {}", guess);
 
-        match guess.cmp(&secret_number) {
-            Ordering::Less => println!("Too small!"),
-            Ordering::Greater => println!("Too big!"),
-            Ordering::Equal => {
-                println!("You win!");
+        let outcome = game.guess(guess);
+        let record = GuessRecord {
+            guess,
+            ordering: outcome.ordering(),
+            attempt: game.attempts(),
+            elapsed: started.elapsed(),
+        };
+        replay.push(record);
+
+        if !text {
+            println!("{}", record.to_json());
+
+            if game.is_over() {
                 break;
             }
+            continue;
+        }
+
+        println!("{}", i18n::message(lang, Key::YouGuessed, &[&guess]));
+
+        match outcome {
+            Outcome::TooSmall => {
+                println!("{}", i18n::message(lang, Key::TooSmallRange, &[&min, &max]))
+            }
+            Outcome::TooBig => println!("{}", i18n::message(lang, Key::TooBigRange, &[&min, &max])),
+            Outcome::Win => {
+                println!("{}", i18n::text(lang, Key::YouWin));
+                break;
+            }
+        }
+
+        if config.hints {
+            print_proximity(&game, lang);
+        }
+        if config.info {
+            let infos = info::analyze(game.range(), &game.transcript());
+            print_info(infos.last().unwrap(), lang);
+        }
+
+        if game.is_lost() {
+            println!("{}", i18n::message(lang, Key::YouLose, &[&game.secret()]));
+            break;
+        }
+    }
+
+    if text {
+        if config.info {
+            print_info_report(&info::analyze(game.range(), &game.transcript()), lang);
+        }
+        print_summary(&game, lang);
+
+        if game.is_evil() {
+            match evil::verify_transcript(game.range(), &game.transcript(), game.secret()) {
+                Ok(()) => {
+                    let secret = game.secret();
+                    println!("{}", i18n::message(lang, Key::EvilConsistent, &[&secret]))
+                }
+                Err(err) => println!("{}", i18n::message(lang, Key::EvilCheated, &[&err])),
+            }
+        }
+    }
+
+    save_replay(&config, &replay);
+
+    // Scripted runs are for tooling, not for the high-score table.
+    if config.script.is_some() {
+        return;
+    }
+
+    let entry = Entry {
+        name: player_name(&config),
+        difficulty: config.label(),
+        min,
+        max,
+        guesses: game.attempts(),
+        won: game.is_won(),
+        score: game.score(),
+        duration: started.elapsed(),
+        date: Date::today(),
+    };
+    if let Err(err) = save_score(entry) {
+        eprintln!("warning: could not save high score: {}", err);
+    }
+
+    match record_stats(game.is_won(), game.attempts()) {
+        Ok(stats) => {
+            println!();
+            print_stats(&stats, lang);
+        }
+        Err(err) => eprintln!("warning: could not save stats: {}", err),
+    }
+}
+
+/// Saves to `--record`, or for unscripted games to the data directory.
+fn save_replay(config: &Config, replay: &Replay) {
+    let (path, in_data_dir) = match &config.record {
+        Some(path) => (Some(PathBuf::from(path)), false),
+        None if config.script.is_none() => (Replay::default_path(Date::today(), replay.seed), true),
+        None => (None, false),
+    };
+    let Some(path) = path else {
+        return;
+    };
+
+    if let Err(err) = replay.save(&path) {
+        eprintln!("warning: could not save replay: {}", err);
+        return;
+    }
+    // Only the replays the game saves on its own are cleaned up.
+    if let Some(dir) = path.parent().filter(|_| in_data_dir) {
+        if let Err(err) = Replay::prune(dir, replay::MAX_SAVED) {
+            eprintln!("warning: could not delete old replays: {}", err);
+        }
+    }
+}
+
+/// Opens the `--script` file, or stdin when there is none or it is `-`.
+fn open_input(script: Option<&str>) -> Box<dyn BufRead> {
+    match script {
+        None | Some("-") => Box::new(io::stdin().lock()),
+        Some(path) => match File::open(path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(err) => {
+                eprintln!("Could not open {}: {}", path, err);
+                process::exit(1);
+            }
+        },
+    }
+}
+
+/// Tells the player how close their last guess was.
+fn print_proximity(game: &Game, lang: Lang) {
+    let history = game.history();
+    let guess = history[history.len() - 1];
+    let proximity = i18n::proximity(lang, hint::proximity(game.range(), game.secret(), guess));
+
+    match history.len().checked_sub(2).map(|i| history[i]) {
+        Some(previous) => {
+            let trend = i18n::trend(lang, hint::trend(game.secret(), previous, guess));
+            println!(
+                "{}",
+                i18n::message(lang, Key::ProximityTrend, &[&proximity, &trend])
+            )
+        }
+        None => println!("{}", i18n::message(lang, Key::Proximity, &[&proximity])),
+    }
+}
+
+/// Shows what is left to search and what the last guess was worth.
+fn print_info(info: &GuessInfo, lang: Lang) {
+    match (info.after.low(), info.after.high()) {
+        (Some(low), Some(high)) if !info.ordering.is_eq() => {
+            let size = info.after.size();
+            println!(
+                "{}",
+                i18n::message(lang, Key::CandidatesLeft, &[&low, &high, &size])
+            )
+        }
+        _ => {}
+    }
+    let (bits, ideal) = (
+        format!("{:.2}", info.bits()),
+        format!("{:.2}", info.ideal_bits()),
+    );
+    println!("{}", i18n::message(lang, Key::BitsEarned, &[&bits, &ideal]));
+}
+
+fn print_info_report(infos: &[GuessInfo], lang: Lang) {
+    println!(
+        "{:>3} {:>10}  {:<6} {:>8} {:>7} {:>7}  {}",
+        "#",
+        i18n::text(lang, Key::ColumnGuess),
+        i18n::text(lang, Key::ColumnResult),
+        i18n::text(lang, Key::ColumnLeft),
+        i18n::text(lang, Key::ColumnBits),
+        i18n::text(lang, Key::ColumnIdeal),
+        i18n::text(lang, Key::ColumnRating)
+    );
+    for (i, info) in infos.iter().enumerate() {
+        println!(
+            "{:>3} {:>10}  {:<7} {:>8} {:>7.2} {:>7.2}  {}",
+            i + 1,
+            info.guess,
+            i18n::ordering(lang, info.ordering),
+            info.after.size(),
+            info.bits(),
+            info.ideal_bits(),
+            i18n::rating(lang, info.rating())
+        );
+    }
+
+    let earned: f64 = infos.iter().map(GuessInfo::bits).sum();
+    let earned = format!("{:.2}", earned);
+    println!("{}", i18n::message(lang, Key::TotalBits, &[&earned]));
+}
+
+fn print_summary(game: &Game, lang: Lang) {
+    let (attempts, optimal) = (game.attempts(), game.optimal_guesses());
+    println!(
+        "{}",
+        i18n::message(lang, Key::GuessesSummary, &[&attempts, &optimal])
+    );
+    if game.hint_penalty() > 0 {
+        let penalty = game.hint_penalty();
+        println!("{}", i18n::message(lang, Key::HintsCost, &[&penalty]));
+    }
+    let score = game.score();
+    println!(
+        "{}",
+        i18n::message(lang, Key::Score, &[&score, &score::MAX_SCORE])
+    );
+}
+
+fn player_name(config: &Config) -> String {
+    config
+        .name
+        .clone()
+        .or_else(|| env::var("USER").ok())
+        .or_else(|| env::var("USERNAME").ok())
+        .unwrap_or_else(|| "anonymous".to_string())
+}
+
+fn save_score(entry: Entry) -> io::Result<()> {
+    let path = HighScores::default_path()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+
+    let mut scores = HighScores::load(&path)?;
+    scores.add(entry);
+    scores.save(&path)
+}
+
+fn daily_game(mut args: impl Iterator<Item = String>) {
+    let mut salt = String::new();
+    let today = Date::today();
+    let mut date = today;
+    let mut rest = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--salt" => {
+                salt = args
+                    .next()
+                    .unwrap_or_else(|| usage_error("`--salt` needs a value"))
+            }
+            "--date" => {
+                date = args
+                    .next()
+                    .unwrap_or_default()
+                    .parse()
+                    .unwrap_or_else(|err: String| usage_error(&err))
+            }
+            _ => rest.push(arg),
+        }
+    }
+
+    let config = Config::from_args(rest).unwrap_or_else(|err| usage_error(&err));
+    reject_flags(&config, "daily", &["--name"]);
+    if date > today {
+        usage_error(&format!("the puzzle for {} is not out yet", date));
+    }
+    let lang = config.lang.unwrap_or_else(Lang::from_env);
+
+    // Only today's puzzle counts. Older ones can be replayed for practice,
+    // but are never recorded.
+    let official = date == today;
+    let name = player_name(&config);
+    let mut saved = official.then(|| {
+        let path = DailyLog::default_path()
+            .unwrap_or_else(|| usage_error("no data directory for daily results"));
+        let log = DailyLog::load(&path).unwrap_or_else(|err| {
+            let path = path.display();
+            eprintln!("{}", i18n::message(lang, Key::CouldNotRead, &[&path, &err]));
+            process::exit(1);
+        });
+        (log, path)
+    });
+    if let Some(result) = saved
+        .as_ref()
+        .and_then(|(log, _)| log.find(date, &name, &salt))
+    {
+        println!(
+            "{}",
+            i18n::message(lang, Key::AlreadyPlayed, &[&name, &date])
+        );
+        println!("{}", result.share_line());
+        return;
+    }
+
+    let secret = daily::secret(&config.range, date, &salt);
+    let mut game =
+        Game::with_secret(config.range.clone(), secret).with_max_attempts(config.max_attempts);
+    let (min, max) = (config.range.start(), config.range.end());
+    let result_of = |game: &Game| DailyResult {
+        date,
+        name: name.clone(),
+        salt: salt.clone(),
+        transcript: game.transcript().into_iter().map(|(_, o)| o).collect(),
+        won: game.is_won(),
+    };
+
+    let title = if official {
+        Key::DailyPuzzle
+    } else {
+        Key::DailyPractice
+    };
+    println!("{}", i18n::message(lang, title, &[&date]));
+    println!("{}", i18n::message(lang, Key::SecretBetween, &[min, max]));
+    if let Some(max_attempts) = game.max_attempts() {
+        println!(
+            "{}",
+            i18n::message(lang, Key::GuessesAllowed, &[&max_attempts])
+        );
+    }
+
+    // Saved as lost before the first guess and again after every guess, so
+    // quitting or being killed part-way cannot earn another try.
+    if let Some((log, path)) = &mut saved {
+        save_daily(log, path, result_of(&game));
+    }
+
+    let mut input = io::stdin().lock();
+
+    while !game.is_over() {
+        println!("{}", i18n::text(lang, Key::PleaseGuess));
+
+        let Some(line) = read_line(&mut input) else {
+            if official {
+                eprintln!("{}", i18n::text(lang, Key::DailyAbandoned));
+                if let Err(err) = record_stats(false, game.attempts()) {
+                    eprintln!("warning: could not save stats: {}", err);
+                }
+            } else {
+                eprintln!("{}", i18n::message(lang, Key::NoMoreInput, &[&secret]));
+            }
+            process::exit(EXIT_EOF);
+        };
+        let guess = match parse_guess(&line, game.range()) {
+            Ok(num) => num,
+            Err(err) => {
+                println!("{}", i18n::input_error(lang, &err));
+                continue;
+            }
+        };
+
+        let key = match game.guess(guess) {
+            Outcome::TooSmall => Key::TooSmall,
+            Outcome::TooBig => Key::TooBig,
+            Outcome::Win => Key::YouWin,
+        };
+        println!("{}", i18n::text(lang, key));
+
+        if let Some((log, path)) = &mut saved {
+            save_daily(log, path, result_of(&game));
+        }
+    }
+
+    if game.is_lost() {
+        println!("{}", i18n::message(lang, Key::YouLose, &[&secret]));
+    }
+
+    // The share line comes last, where it is easy to copy.
+    if official {
+        match record_stats(game.is_won(), game.attempts()) {
+            Ok(stats) => {
+                println!();
+                print_stats(&stats, lang);
+            }
+            Err(err) => eprintln!("warning: could not save stats: {}", err),
+        }
+    }
+
+    let result = result_of(&game);
+    println!();
+    if official {
+        println!("{}", result.share_line());
+    } else {
+        println!(
+            "{}",
+            daily::practice_line(date, &result.transcript, result.won)
+        );
+    }
+}
+
+fn save_daily(log: &mut DailyLog, path: &Path, result: DailyResult) {
+    log.update(result);
+    if let Err(err) = log.save(path) {
+        eprintln!("warning: could not save daily result: {}", err);
+    }
+}
+
+fn load_replay(path: &str, lang: Lang) -> Replay {
+    Replay::load(Path::new(path)).unwrap_or_else(|err| {
+        // A damaged replay says what is wrong with it in `lang`; errors
+        // from the system keep their own wording.
+        let reason = match err.get_ref().and_then(|err| err.downcast_ref()) {
+            Some(err) => i18n::replay_error(lang, err),
+            None => err.to_string(),
+        };
+        eprintln!(
+            "{}",
+            i18n::message(lang, Key::CouldNotRead, &[&path, &reason])
+        );
+        process::exit(1);
+    })
+}
+
+fn replay(mut args: impl Iterator<Item = String>) {
+    let mut path = None;
+    let mut speed = 1.0;
+    let mut lang = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--lang" => lang = Some(lang_arg(args.next())),
+            "--speed" => {
+                speed = args
+                    .next()
+                    .and_then(|n| n.parse::<f64>().ok())
+                    .filter(|n| *n == 0.0 || (MIN_REPLAY_SPEED..=MAX_REPLAY_SPEED).contains(n))
+                    .unwrap_or_else(|| {
+                        usage_error(&format!(
+                            "`--speed` expects 0, or a number from {} to {}",
+                            MIN_REPLAY_SPEED, MAX_REPLAY_SPEED
+                        ))
+                    })
+            }
+            _ if path.is_none() => path = Some(arg),
+            _ => usage_error(&format!("unexpected argument `{}`", arg)),
+        }
+    }
+
+    let path = path.unwrap_or_else(|| usage_error("`replay` needs a file"));
+    let lang = lang.unwrap_or_else(Lang::from_env);
+    let replay = load_replay(&path, lang);
+
+    println!(
+        "{}",
+        i18n::message(
+            lang,
+            Key::Replaying,
+            &[&replay.seed, replay.range.start(), replay.range.end()]
+        )
+    );
+
+    // `--speed 0` skips the waiting altogether.
+    let mut shown = Duration::ZERO;
+    for record in &replay.guesses {
+        if speed > 0.0 {
+            let gap = record.elapsed.saturating_sub(shown).as_secs_f64() / speed;
+            thread::sleep(Duration::try_from_secs_f64(gap).unwrap_or(Duration::MAX));
+        }
+        shown = record.elapsed;
+
+        println!("{}", i18n::message(lang, Key::YouGuessed, &[&record.guess]));
+        println!(
+            "{}",
+            i18n::text(
+                lang,
+                match record.ordering {
+                    Ordering::Less => Key::TooSmall,
+                    Ordering::Greater => Key::TooBig,
+                    Ordering::Equal => Key::YouWin,
+                }
+            )
+        );
+    }
+}
+
+fn verify(mut args: impl Iterator<Item = String>) {
+    let mut path = None;
+    let mut lang = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--lang" => lang = Some(lang_arg(args.next())),
+            _ if path.is_none() => path = Some(arg),
+            _ => usage_error(&format!("unexpected argument `{}`", arg)),
+        }
+    }
+
+    let path = path.unwrap_or_else(|| usage_error("`verify` needs a file"));
+    let lang = lang.unwrap_or_else(Lang::from_env);
+    let replay = load_replay(&path, lang);
+    match replay.verify() {
+        Ok(game) => {
+            let ending = if game.is_won() {
+                Key::GameWon
+            } else if game.is_lost() {
+                Key::GameLost
+            } else {
+                Key::GameUnfinished
+            };
+            println!(
+                "{}",
+                i18n::message(
+                    lang,
+                    Key::VerifyMatches,
+                    &[
+                        &path,
+                        &replay.guesses.len(),
+                        &replay.seed,
+                        &i18n::text(lang, ending)
+                    ]
+                )
+            );
+        }
+        Err(err) => {
+            println!("{}: {}", path, i18n::verify_error(lang, &err));
+            process::exit(EXIT_MISMATCH);
+        }
+    }
+}
+
+fn record_stats(won: bool, guesses: u32) -> io::Result<Stats> {
+    let path = Stats::default_path()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+
+    let mut stats = Stats::load(&path)?;
+    stats.record(won, guesses);
+    stats.save(&path)?;
+    Ok(stats)
+}
+
+fn print_stats(stats: &Stats, lang: Lang) {
+    let Some(win_rate) = stats.win_rate() else {
+        println!("{}", i18n::text(lang, Key::NoGamesYet));
+        return;
+    };
+
+    let percent = format!("{:.0}", win_rate * 100.0);
+    println!(
+        "{}",
+        i18n::message(
+            lang,
+            Key::StatsPlayed,
+            &[&stats.games, &stats.wins, &percent]
+        )
+    );
+    if let (Some(mean), Some(median)) = (stats.mean_guesses(), stats.median_guesses()) {
+        let (mean, median) = (format!("{:.2}", mean), format!("{:.1}", median));
+        println!(
+            "{}",
+            i18n::message(lang, Key::StatsGuesses, &[&mean, &median])
+        );
+    }
+    println!(
+        "{}",
+        i18n::message(
+            lang,
+            Key::StatsStreak,
+            &[&stats.current_streak, &stats.best_streak]
+        )
+    );
+
+    print_histogram(&stats.distribution);
+}
+
+/// One bar per number of guesses, scaled so the longest is 40 wide.
+fn print_histogram(distribution: &BTreeMap<u32, u32>) {
+    let widest = distribution.values().copied().max().unwrap_or(0);
+    for (guesses, count) in distribution {
+        let bar = (*count as u64 * 40).div_ceil(u64::from(widest.max(1)));
+        println!("{:>8} {:>7}  {}", guesses, count, "#".repeat(bar as usize));
+    }
+}
+
+fn stats(mut args: impl Iterator<Item = String>) {
+    let mut merge = Vec::new();
+    let mut lang = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--merge" => merge.push(
+                args.next()
+                    .unwrap_or_else(|| usage_error("`--merge` needs a file")),
+            ),
+            "--lang" => lang = Some(lang_arg(args.next())),
+            _ => usage_error(&format!("unexpected argument `{}`", arg)),
+        }
+    }
+
+    let lang = lang.unwrap_or_else(Lang::from_env);
+    let path = Stats::default_path();
+    let mut stats = path
+        .as_deref()
+        .map(Stats::load)
+        .unwrap_or_else(|| Ok(Stats::default()))
+        .unwrap_or_else(|err| {
+            eprintln!("warning: could not read stats: {}", err);
+            Stats::default()
+        });
+
+    if !merge.is_empty() {
+        for file in &merge {
+            match Stats::load(Path::new(file)) {
+                Ok(other) => stats.merge(&other),
+                Err(err) => {
+                    eprintln!("{}", i18n::message(lang, Key::CouldNotRead, &[file, &err]));
+                    process::exit(1);
+                }
+            }
+        }
+
+        let saved = path
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))
+            .and_then(|path| stats.save(&path));
+        if let Err(err) = saved {
+            eprintln!("Could not save merged stats: {}", err);
+            process::exit(1);
+        }
+    }
+
+    print_stats(&stats, lang);
+}
+
+fn scores(mut args: impl Iterator<Item = String>) {
+    let mut top = 10;
+    let mut lang = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--top" => {
+                top = args
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .unwrap_or_else(|| usage_error("`--top` expects a number"))
+            }
+            "--lang" => lang = Some(lang_arg(args.next())),
+            _ => usage_error(&format!("unexpected argument `{}`", arg)),
+        }
+    }
+
+    let lang = lang.unwrap_or_else(Lang::from_env);
+    let scores = HighScores::default_path()
+        .map(|path| HighScores::load(&path))
+        .unwrap_or_else(|| Ok(HighScores::default()))
+        .unwrap_or_else(|err| {
+            eprintln!("{}", i18n::message(lang, Key::ScoresUnreadable, &[&err]));
+            HighScores::default()
+        });
+
+    let mut printed = false;
+    for difficulty in scores.difficulties() {
+        let entries = scores.top(difficulty, top);
+        if entries.is_empty() {
+            continue;
+        }
+
+        println!("{}", difficulty);
+        for (rank, entry) in entries.iter().enumerate() {
+            let guesses = format!("{:>3}", entry.guesses);
+            let seconds = format!("{:>7.1}", entry.duration.as_secs_f64());
+            println!(
+                "{:>3}. {:<16} {}",
+                rank + 1,
+                entry.name,
+                i18n::message(
+                    lang,
+                    Key::ScoresEntry,
+                    &[
+                        &guesses,
+                        &entry.min,
+                        &entry.max,
+                        &seconds,
+                        &entry.date,
+                        &entry.score
+                    ]
+                )
+            );
+        }
+        printed = true;
+    }
+
+    if !printed {
+        println!("{}", i18n::text(lang, Key::NoScoresYet));
+    }
+}
+
+fn simulate(mut args: impl Iterator<Item = String>) {
+    let mut games = 1000;
+    let mut names = Vec::new();
+    let mut rest = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--games" => {
+                games = args
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .unwrap_or_else(|| usage_error("`--games` expects a number"))
+            }
+            "--strategy" => match args.next() {
+                Some(name) if STRATEGY_NAMES.contains(&name.as_str()) => names.push(name),
+                _ => usage_error("`--strategy` expects binary, random or linear"),
+            },
+            _ => rest.push(arg),
+        }
+    }
+
+    let config = Config::from_args(rest).unwrap_or_else(|err| usage_error(&err));
+    reject_flags(&config, "simulate", &["--seed", "--evil"]);
+    let seed = config.seed.unwrap_or_else(rand::random);
+    let lang = config.lang.unwrap_or_else(Lang::from_env);
+    if names.is_empty() {
+        names = STRATEGY_NAMES.iter().map(|name| name.to_string()).collect();
+    }
+
+    println!(
+        "{}",
+        i18n::message(
+            lang,
+            Key::SimulateIntro,
+            &[&games, config.range.start(), config.range.end(), &seed]
+        )
+    );
+
+    for name in &names {
+        let mut strategy = strategy::strategy_by_name(name, seed).unwrap();
+        let summary = strategy::simulate(strategy.as_mut(), &config, games, seed);
+
+        let mean_guesses = format!("{:.2}", summary.mean_guesses());
+        let mean_score = format!("{:.1}", summary.mean_score());
+        println!();
+        println!(
+            "{}",
+            i18n::message(
+                lang,
+                Key::SimulateSummary,
+                &[
+                    &strategy.name(),
+                    &mean_guesses,
+                    &summary.max_guesses,
+                    &summary.wins,
+                    &summary.games,
+                    &mean_score
+                ]
+            )
+        );
+        print_histogram(&summary.distribution);
+    }
+}
+
+fn reverse(args: impl Iterator<Item = String>) {
+    let config = Config::from_args(args).unwrap_or_else(|err| usage_error(&err));
+    reject_flags(&config, "reverse", &[]);
+    let lang = config.lang.unwrap_or_else(Lang::from_env);
+    let mut reverser = Reverser::new(config.range.clone());
+
+    println!(
+        "{}",
+        i18n::message(
+            lang,
+            Key::ReverseIntro,
+            &[config.range.start(), config.range.end()]
+        )
+    );
+
+    loop {
+        if let Some(number) = reverser.found() {
+            let guesses = reverser.guesses();
+            println!(
+                "{}",
+                i18n::message(lang, Key::ReverseFound, &[&number, &guesses])
+            );
+            break;
+        }
+
+        let guess = reverser.guess();
+        println!("{}", i18n::message(lang, Key::ReverseAsk, &[&guess]));
+
+        let Some(line) = read_line(&mut io::stdin().lock()) else {
+            eprintln!("{}", i18n::text(lang, Key::InputClosed));
+            process::exit(EXIT_EOF);
+        };
+
+        let answer: Answer = match line.parse() {
+            Ok(answer) => answer,
+            Err(_) => {
+                println!("{}", i18n::message(lang, Key::NotAnAnswer, &[&line.trim()]));
+                continue;
+            }
+        };
+
+        if let Err(cheating) = reverser.answer(answer) {
+            println!("{}", i18n::cheating(lang, &cheating));
+            process::exit(EXIT_CHEATING);
+        }
+    }
+}
+
+fn cows(mut args: impl Iterator<Item = String>) {
+    let mut digits = 4;
+    let mut max_attempts = None;
+    let mut seed = None;
+    let mut solve = false;
+    let mut lang = None;
+
+    while let Some(arg) = args.next() {
+        let mut number = |flag: &str| -> u64 {
+            args.next()
+                .and_then(|n| n.parse().ok())
+                .unwrap_or_else(|| usage_error(&format!("`{}` expects a number", flag)))
+        };
+        match arg.as_str() {
+            "--digits" => {
+                digits = usize::try_from(number("--digits"))
+                    .unwrap_or_else(|_| usage_error("`--digits` must be between 1 and 10"))
+            }
+            "--max-guesses" => {
+                max_attempts = Some(
+                    u32::try_from(number("--max-guesses"))
+                        .unwrap_or_else(|_| usage_error("`--max-guesses` is too large")),
+                )
+            }
+            "--seed" => seed = Some(number("--seed")),
+            "--solve" => solve = true,
+            "--lang" => lang = Some(lang_arg(args.next())),
+            _ => usage_error(&format!("unexpected argument `{}`", arg)),
+        }
+    }
+    if !(1..=bulls_cows::MAX_DIGITS).contains(&digits) {
+        usage_error("`--digits` must be between 1 and 10");
+    }
+    if max_attempts == Some(0) {
+        usage_error("`--max-guesses` must be at least 1");
+    }
+
+    let seed = seed.unwrap_or_else(rand::random);
+    let lang = lang.unwrap_or_else(Lang::from_env);
+    let mut game = CowsGame::new(digits, &mut seeded_rng(seed));
+    // Only built for `--solve`: with many digits it lists millions of codes.
+    let mut solver = solve.then(|| Solver::new(digits));
+
+    println!("{}", i18n::message(lang, Key::CowsIntro, &[&seed]));
+    println!("{}", i18n::message(lang, Key::CowsDigits, &[&digits]));
+
+    while !game.is_won() {
+        if max_attempts.is_some_and(|max| game.attempts() >= max) {
+            println!("{}", i18n::message(lang, Key::CowsLose, &[game.secret()]));
+            return;
+        }
+
+        let guess = if let Some(solver) = &solver {
+            let guess = solver.next_guess();
+            println!("{}", i18n::message(lang, Key::CowsSolverGuess, &[&guess]));
+            guess
+        } else {
+            println!("{}", i18n::text(lang, Key::PleaseGuess));
+            let Some(line) = read_line(&mut io::stdin().lock()) else {
+                let secret = game.secret();
+                eprintln!("{}", i18n::message(lang, Key::CowsNoMoreInput, &[secret]));
+                process::exit(EXIT_EOF);
+            };
+            match Code::parse(&line, digits) {
+                Ok(guess) => guess,
+                Err(err) => {
+                    println!("{}", i18n::code_error(lang, &err));
+                    continue;
+                }
+            }
+        };
+
+        let result = game.guess(guess.clone());
+        if let Some(solver) = &mut solver {
+            solver.feedback(&guess, result);
+        }
+        println!("{}", i18n::cows_score(lang, result));
+    }
+
+    let attempts = game.attempts();
+    let done = if solve { Key::CowsSolved } else { Key::CowsWin };
+    println!("{}", i18n::message(lang, done, &[&attempts]));
+}
+
+fn word_game(mut args: impl Iterator<Item = String>) {
+    let mut path = None;
+    let mut len = 5;
+    let mut rest = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--words" => {
+                path = Some(
+                    args.next()
+                        .unwrap_or_else(|| usage_error("`--words` needs a file")),
+                )
+            }
+            "--length" => {
+                len = args
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .unwrap_or_else(|| usage_error("`--length` expects a number"))
+            }
+            _ => rest.push(arg),
+        }
+    }
+
+    let config = Config::from_args(rest).unwrap_or_else(|err| usage_error(&err));
+    reject_flags(&config, "words", &["--seed", "--name", "--script"]);
+    let lang = config.lang.unwrap_or_else(Lang::from_env);
+    // `--words` must exist; the data directory's list is optional.
+    let path = path
+        .map(PathBuf::from)
+        .or_else(|| WordList::default_path().filter(|path| path.exists()));
+    let (list, source) = match &path {
+        Some(path) => match WordList::load(path) {
+            Ok(list) => (list, path.display().to_string()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                let path = path.display();
+                eprintln!("{}", i18n::message(lang, Key::WordsNotFound, &[&path]));
+                process::exit(1);
+            }
+            Err(err) => {
+                let path = path.display();
+                eprintln!("{}", i18n::message(lang, Key::CouldNotRead, &[&path, &err]));
+                process::exit(1);
+            }
+        },
+        None => (
+            WordList::builtin(),
+            i18n::text(lang, Key::BuiltinWordList).to_string(),
+        ),
+    };
+
+    let seed = config.seed.unwrap_or_else(rand::random);
+    let Some(game) = WordGame::new(list.with_length(len), &mut seeded_rng(seed)) else {
+        eprintln!(
+            "{}",
+            i18n::message(lang, Key::WordsNoneOfLength, &[&source, &len])
+        );
+        process::exit(1);
+    };
+    let mut game =
+        game.with_max_attempts(config.max_attempts.unwrap_or(words::DEFAULT_MAX_ATTEMPTS));
+    let prompt = config.script.is_none();
+    let mut input = open_input(config.script.as_deref());
+
+    let (word_len, max_attempts) = (game.word_len(), game.max_attempts());
+    println!("{}", i18n::message(lang, Key::WordsIntro, &[&seed]));
+    println!(
+        "{}",
+        i18n::message(lang, Key::WordsLength, &[&word_len, &max_attempts])
+    );
+
+    let started = Instant::now();
+
+    while !game.is_over() {
+        if prompt {
+            println!("{}", i18n::text(lang, Key::PleaseGuess));
+        }
+
+        let Some(line) = read_line(input.as_mut()) else {
+            let secret = game.secret();
+            eprintln!("{}", i18n::message(lang, Key::WordsNoMoreInput, &[&secret]));
+            process::exit(EXIT_EOF);
+        };
+
+        match game.guess(&line) {
+            Ok(marks) => {
+                let marks: Vec<String> = marks.iter().map(|m| m.symbol().to_string()).collect();
+                let (word, _) = game.history().last().unwrap();
+                let letters: Vec<String> = word.chars().map(String::from).collect();
+                println!("{}", letters.join(" "));
+                println!("{}", marks.join(" "));
+            }
+            Err(err) => println!("{}", i18n::word_error(lang, &err)),
+        }
+    }
+
+    if game.is_won() {
+        println!("{}", i18n::text(lang, Key::YouWin));
+    } else {
+        let secret = game.secret();
+        println!("{}", i18n::message(lang, Key::WordsLose, &[&secret]));
+    }
+    let score = game.score();
+    println!(
+        "{}",
+        i18n::message(lang, Key::Score, &[&score, &score::MAX_SCORE])
+    );
+
+    if config.script.is_some() {
+        return;
+    }
+
+    let len = game.word_len() as i64;
+    let entry = Entry {
+        name: player_name(&config),
+        difficulty: "words".to_string(),
+        min: len,
+        max: len,
+        guesses: game.attempts(),
+        won: game.is_won(),
+        score: game.score(),
+        duration: started.elapsed(),
+        date: Date::today(),
+    };
+    if let Err(err) = save_score(entry) {
+        eprintln!("warning: could not save high score: {}", err);
+    }
+}
+
+fn party(mut args: impl Iterator<Item = String>) {
+    let mut names = Vec::new();
+    let mut mode = Mode::HotSeat;
+    let mut rest = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--players" => {
+                let list = args
+                    .next()
+                    .unwrap_or_else(|| usage_error("`--players` needs a list of names"));
+                names.extend(
+                    list.split(',')
+                        .map(str::trim)
+                        .filter(|name| !name.is_empty())
+                        .map(String::from),
+                );
+            }
+            "--mode" => {
+                mode = args
+                    .next()
+                    .unwrap_or_default()
+                    .parse()
+                    .unwrap_or_else(|err: String| usage_error(&err))
+            }
+            _ => rest.push(arg),
+        }
+    }
+
+    let config = Config::from_args(rest).unwrap_or_else(|err| usage_error(&err));
+    reject_flags(&config, "party", &["--seed"]);
+    if names.len() < 2 {
+        usage_error("`--players` needs at least two names");
+    }
+    if (1..names.len()).any(|i| names[..i].contains(&names[i])) {
+        usage_error("every player needs a different name");
+    }
+
+    let seed = config.seed.unwrap_or_else(rand::random);
+    let lang = config.lang.unwrap_or_else(Lang::from_env);
+    let mut game = Match::new(mode, names.clone(), &config, &mut seeded_rng(seed));
+    let (min, max) = (config.range.start(), config.range.end());
+
+    let (title, between) = match mode {
+        Mode::HotSeat => (Key::PartyHotSeat, Key::SecretBetween),
+        Mode::Race => (Key::PartyRace, Key::PartyRaceBetween),
+    };
+    println!(
+        "{}",
+        i18n::message(lang, title, &[&names.join(", "), &seed])
+    );
+    println!("{}", i18n::message(lang, between, &[min, max]));
+
+    let mut input = io::stdin().lock();
+
+    while let Some(player) = game.current() {
+        let name = player.name.clone();
+        println!("{}", i18n::message(lang, Key::PartyPrompt, &[&name]));
+
+        let Some(line) = read_line(&mut input) else {
+            eprintln!("{}", i18n::text(lang, Key::InputClosed));
+            process::exit(EXIT_EOF);
+        };
+        let guess = match parse_guess(&line, &config.range) {
+            Ok(num) => num,
+            Err(err) => {
+                println!("{}", i18n::input_error(lang, &err));
+                continue;
+            }
+        };
+
+        match game.guess(guess) {
+            Outcome::TooSmall => println!("{}", i18n::text(lang, Key::TooSmall)),
+            Outcome::TooBig => println!("{}", i18n::text(lang, Key::TooBig)),
+            Outcome::Win => println!("{}", i18n::message(lang, Key::PartyGotIt, &[&name])),
+        }
+
+        let player = game.players().iter().find(|p| p.name == name).unwrap();
+        if player.game.is_lost() {
+            let secret = player.game.secret();
+            println!(
+                "{}",
+                i18n::message(lang, Key::PartyOutOfGuesses, &[&name, &secret])
+            );
+        }
+    }
+
+    // Pad "won" and "lost" to the same width so the guesses line up.
+    let (won, lost) = (
+        i18n::text(lang, Key::PartyWon),
+        i18n::text(lang, Key::PartyLost),
+    );
+    let width = won.chars().count().max(lost.chars().count());
+
+    println!();
+    println!("{}", i18n::text(lang, Key::PartyRanking));
+    for standing in game.ranking() {
+        let player = game
+            .players()
+            .iter()
+            .find(|p| p.name == standing.name)
+            .unwrap();
+        let history: Vec<String> = player.game.history().iter().map(i64::to_string).collect();
+        let result = format!("{:<width$}", if standing.won { won } else { lost });
+        println!(
+            "{:>3}. {:<16} {}",
+            standing.rank,
+            standing.name,
+            i18n::message(
+                lang,
+                Key::PartyStanding,
+                &[&result, &standing.guesses, &history.join(" ")]
+            )
+        );
+    }
+}
+
+/// Pulls `--addr HOST:PORT` out of `args`, returning it and the rest.
+fn take_addr(mut args: impl Iterator<Item = String>) -> (String, Vec<String>) {
+    let mut addr = DEFAULT_ADDR.to_string();
+    let mut rest = Vec::new();
+
+    while let Some(arg) = args.next() {
+        if arg == "--addr" {
+            addr = args
+                .next()
+                .unwrap_or_else(|| usage_error("`--addr` needs a value"));
+        } else {
+            rest.push(arg);
+        }
+    }
+
+    (addr, rest)
+}
+
+fn serve(args: impl Iterator<Item = String>) {
+    let (addr, rest) = take_addr(args);
+    let config = Config::from_args(rest).unwrap_or_else(|err| usage_error(&err));
+    reject_flags(&config, "serve", &["--seed", "--evil"]);
+    let seed = config.seed.unwrap_or_else(rand::random);
+    let lang = config.lang.unwrap_or_else(Lang::from_env);
+
+    let listener = TcpListener::bind(&addr).unwrap_or_else(|err| {
+        eprintln!(
+            "{}",
+            i18n::message(lang, Key::ServeCannotListen, &[&addr, &err])
+        );
+        process::exit(1);
+    });
+    let local = listener.local_addr().map_or(addr, |addr| addr.to_string());
+    println!(
+        "{}",
+        i18n::message(
+            lang,
+            Key::ServeIntro,
+            &[config.range.start(), config.range.end(), &local, &seed]
+        )
+    );
+
+    if let Err(err) = net::serve(listener, config, seed) {
+        eprintln!("{}", i18n::message(lang, Key::ServeStopped, &[&err]));
+        process::exit(1);
+    }
+}
+
+fn client(args: impl Iterator<Item = String>) {
+    let (addr, rest) = take_addr(args);
+    if let Some(arg) = rest.first() {
+        usage_error(&format!("unexpected argument `{}`", arg));
+    }
+
+    let fail = |err: io::Error| -> ! {
+        eprintln!("Connection to {} failed: {}", addr, err);
+        process::exit(1);
+    };
+
+    let (mut client, greeting) = Client::connect(&addr).unwrap_or_else(|err| fail(err));
+    println!("{}", greeting);
+
+    loop {
+        let Some(line) = read_line(&mut io::stdin().lock()) else {
+            client.send(Command::Quit).unwrap_or_else(|err| fail(err));
+            return;
+        };
+
+        // A bare number is shorthand for `GUESS n`.
+        let line = match line.trim().parse::<i64>() {
+            Ok(n) => Command::Guess(n).to_string(),
+            Err(_) => line.trim().to_string(),
+        };
+
+        let reply = client.send_line(&line).unwrap_or_else(|err| fail(err));
+        println!("{}", reply);
+
+        if reply == Reply::Bye {
+            return;
         }
     }
 }
@@ -0,0 +1,272 @@
+//! Several players sharing one terminal, taking turns in player order.
+//!
+//! In a hot-seat match everyone guesses the same secret and the first
+//! right guess ends the match. In a race everyone has a secret of their
+//! own and plays until they find it or run out of guesses; the fewest
+//! guesses wins.
+
+use crate::{Config, Game, Outcome};
+use rand::Rng;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    HotSeat,
+    Race,
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Mode::HotSeat => "hotseat",
+            Mode::Race => "race",
+        })
+    }
+}
+
+impl FromStr for Mode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Mode, String> {
+        match s.to_ascii_lowercase().as_str() {
+            "hotseat" | "hot-seat" => Ok(Mode::HotSeat),
+            "race" => Ok(Mode::Race),
+            _ => Err(format!("unknown mode `{}` (expected hotseat or race)", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Player {
+    pub name: String,
+    pub game: Game,
+}
+
+/// A player's place in the final ranking.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    /// Starts at one; players who did equally well share a rank.
+    pub rank: usize,
+    pub name: String,
+    pub won: bool,
+    pub guesses: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Match {
+    mode: Mode,
+    players: Vec<Player>,
+    turn: usize,
+}
+
+impl Match {
+    /// Starts a match for `names` using the range and guess limit from
+    /// `config`.
+    ///
+    /// Panics if `names` is empty.
+    pub fn new<R: Rng + ?Sized>(
+        mode: Mode,
+        names: Vec<String>,
+        config: &Config,
+        rng: &mut R,
+    ) -> Match {
+        assert!(!names.is_empty(), "A match needs at least one player.");
+
+        let shared = rng.gen_range(config.range.clone());
+        let players = names
+            .into_iter()
+            .map(|name| {
+                let secret = match mode {
+                    Mode::HotSeat => shared,
+                    Mode::Race => rng.gen_range(config.range.clone()),
+                };
+                let game = Game::with_secret(config.range.clone(), secret)
+                    .with_max_attempts(config.max_attempts);
+                Player { name, game }
+            })
+            .collect();
+
+        Match {
+            mode,
+            players,
+            turn: 0,
+        }
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    /// The player whose turn it is, or `None` once the match is over.
+    pub fn current(&self) -> Option<&Player> {
+        if self.is_over() {
+            None
+        } else {
+            Some(&self.players[self.turn])
+        }
+    }
+
+    /// Makes a guess for the current player and passes the turn on to the
+    /// next player who is still playing.
+    ///
+    /// Panics if the match is over.
+    pub fn guess(&mut self, guess: i64) -> Outcome {
+        assert!(!self.is_over(), "Cannot guess after the match is over.");
+
+        let outcome = self.players[self.turn].game.guess(guess);
+
+        if !self.is_over() {
+            loop {
+                self.turn = (self.turn + 1) % self.players.len();
+                if !self.players[self.turn].game.is_over() {
+                    break;
+                }
+            }
+        }
+
+        outcome
+    }
+
+    pub fn is_over(&self) -> bool {
+        let mut games = self.players.iter().map(|player| &player.game);
+
+        match self.mode {
+            Mode::HotSeat => games.clone().any(Game::is_won) || games.all(Game::is_over),
+            Mode::Race => games.all(Game::is_over),
+        }
+    }
+
+    /// Winners first by fewest guesses, then everyone who did not win.
+    pub fn ranking(&self) -> Vec<Standing> {
+        let mut standings: Vec<Standing> = self
+            .players
+            .iter()
+            .map(|player| Standing {
+                rank: 0,
+                name: player.name.clone(),
+                won: player.game.is_won(),
+                guesses: player.game.attempts(),
+            })
+            .collect();
+        let key = |standing: &Standing| (!standing.won, standing.won.then_some(standing.guesses));
+        standings.sort_by_key(key);
+
+        for i in 0..standings.len() {
+            standings[i].rank = if i > 0 && key(&standings[i]) == key(&standings[i - 1]) {
+                standings[i - 1].rank
+            } else {
+                i + 1
+            };
+        }
+
+        standings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seeded_rng;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn wrong_guess(game: &Game) -> i64 {
+        if game.secret() == 1 {
+            2
+        } else {
+            1
+        }
+    }
+
+    #[test]
+    fn parses_modes() {
+        assert_eq!("hotseat".parse(), Ok(Mode::HotSeat));
+        assert_eq!("Race".parse(), Ok(Mode::Race));
+        assert!("solo".parse::<Mode>().is_err());
+    }
+
+    #[test]
+    fn hot_seat_ends_at_the_first_right_guess() {
+        let config = Config::default();
+        let mut game = Match::new(
+            Mode::HotSeat,
+            names(&["ann", "bob", "cat"]),
+            &config,
+            &mut seeded_rng(1),
+        );
+        let secret = game.players()[0].game.secret();
+        assert!(game.players().iter().all(|p| p.game.secret() == secret));
+
+        let wrong = wrong_guess(&game.players()[0].game);
+        game.guess(wrong);
+        assert_eq!(game.current().unwrap().name, "bob");
+        assert_eq!(game.guess(secret), Outcome::Win);
+        assert!(game.is_over());
+        assert!(game.current().is_none());
+
+        let ranking = game.ranking();
+        assert_eq!(ranking[0].name, "bob");
+        assert_eq!(ranking[0].rank, 1);
+        assert!(ranking[1..].iter().all(|s| s.rank == 2 && !s.won));
+        assert_eq!(game.players()[0].game.history(), [wrong]);
+    }
+
+    #[test]
+    fn race_ranks_by_fewest_guesses() {
+        let config = Config {
+            max_attempts: Some(3),
+            ..Config::default()
+        };
+        let mut game = Match::new(
+            Mode::Race,
+            names(&["ann", "bob", "cat"]),
+            &config,
+            &mut seeded_rng(2),
+        );
+        let secrets: Vec<i64> = game.players().iter().map(|p| p.game.secret()).collect();
+        let wrong: Vec<i64> = game
+            .players()
+            .iter()
+            .map(|p| wrong_guess(&p.game))
+            .collect();
+
+        // Round 1: ann wins, bob and cat miss.
+        game.guess(secrets[0]);
+        game.guess(wrong[1]);
+        game.guess(wrong[2]);
+        // Round 2: ann is done, so bob and cat keep alternating.
+        assert_eq!(game.current().unwrap().name, "bob");
+        game.guess(wrong[1]);
+        game.guess(secrets[2]);
+        game.guess(wrong[1]);
+        assert!(game.is_over());
+
+        let ranking = game.ranking();
+        let order: Vec<(&str, usize)> = ranking.iter().map(|s| (s.name.as_str(), s.rank)).collect();
+        assert_eq!(order, [("ann", 1), ("cat", 2), ("bob", 3)]);
+    }
+
+    #[test]
+    fn equal_results_share_a_rank() {
+        let config = Config::default();
+        let mut game = Match::new(
+            Mode::Race,
+            names(&["ann", "bob"]),
+            &config,
+            &mut seeded_rng(3),
+        );
+        let secrets: Vec<i64> = game.players().iter().map(|p| p.game.secret()).collect();
+
+        game.guess(secrets[0]);
+        game.guess(secrets[1]);
+
+        assert!(game.ranking().iter().all(|s| s.rank == 1 && s.won));
+    }
+}
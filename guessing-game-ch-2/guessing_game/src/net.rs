@@ -0,0 +1,120 @@
+//! Playing over TCP with the line protocol from [`crate::protocol`].
+
+use crate::i18n::{self, Key, Lang};
+use crate::protocol::{Command, Reply, Session};
+use crate::{seeded_rng, Config};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::thread;
+use std::time::Duration;
+
+/// How many accepts in a row may fail before [`serve`] gives up.
+pub const MAX_ACCEPT_FAILURES: u32 = 10;
+
+/// Accepts connections on `listener`, one thread and one game each.
+///
+/// Connection number `n` (from zero) draws its secrets from
+/// `seed + n`, so a seeded server deals the same games every run.
+/// A failed accept, e.g. a client that gave up or too many open files,
+/// is logged and retried after a pause that doubles each time, up to a
+/// second. Only after [`MAX_ACCEPT_FAILURES`] failures in a row does this
+/// return, with the last error; otherwise it never returns.
+pub fn serve(listener: TcpListener, config: Config, seed: u64) -> io::Result<()> {
+    let mut connections: u64 = 0;
+    let mut failures = 0;
+
+    loop {
+        let stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(err) => {
+                failures += 1;
+                if failures >= MAX_ACCEPT_FAILURES {
+                    return Err(err);
+                }
+                let lang = config.lang.unwrap_or_else(Lang::from_env);
+                eprintln!("{}", i18n::message(lang, Key::AcceptFailed, &[&err]));
+                thread::sleep(Duration::from_millis(1 << failures).min(Duration::from_secs(1)));
+                continue;
+            }
+        };
+        failures = 0;
+
+        let session = Session::new(config.clone(), seeded_rng(seed.wrapping_add(connections)));
+        connections += 1;
+
+        thread::spawn(move || {
+            // A client hanging up midway is not the server's problem.
+            let _ = handle_connection(stream, session);
+        });
+    }
+}
+
+/// Writes `line` and its newline in a single call, so small messages are
+/// not held back waiting for each other.
+fn write_line(stream: &mut TcpStream, line: impl std::fmt::Display) -> io::Result<()> {
+    stream.write_all(format!("{}\n", line).as_bytes())
+}
+
+fn handle_connection(stream: TcpStream, mut session: Session) -> io::Result<()> {
+    stream.set_nodelay(true)?;
+    let mut writer = stream.try_clone()?;
+    let reader = BufReader::new(stream);
+
+    write_line(&mut writer, session.greeting())?;
+
+    for line in reader.lines() {
+        let reply = session.handle(&line?);
+        write_line(&mut writer, &reply)?;
+
+        if reply == Reply::Bye {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// A connection to a `guessing_game serve` server.
+pub struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Client {
+    /// Connects and returns the client along with the server's greeting.
+    pub fn connect<A: ToSocketAddrs>(addr: A) -> io::Result<(Client, Reply)> {
+        let stream = TcpStream::connect(addr)?;
+        stream.set_nodelay(true)?;
+        let mut client = Client {
+            writer: stream.try_clone()?,
+            reader: BufReader::new(stream),
+        };
+
+        let greeting = client.read_reply()?;
+        Ok((client, greeting))
+    }
+
+    /// Sends one command and waits for its reply.
+    pub fn send(&mut self, command: Command) -> io::Result<Reply> {
+        self.send_line(&command.to_string())
+    }
+
+    /// Sends a raw line, which lets callers try out malformed commands.
+    pub fn send_line(&mut self, line: &str) -> io::Result<Reply> {
+        write_line(&mut self.writer, line)?;
+        self.read_reply()
+    }
+
+    fn read_reply(&mut self) -> io::Result<Reply> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "server closed the connection",
+            ));
+        }
+
+        line.parse()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
@@ -0,0 +1,244 @@
+//! The line protocol spoken by `guessing_game serve`.
+//!
+//! Clients send one command per line: `GUESS n`, `NEW` or `QUIT`. The
+//! server answers every command with exactly one reply line:
+//!
+//! * `READY min max` when a game starts, including right after connecting
+//! * `LESS` or `GREATER` when the guess is less or greater than the secret
+//! * `WIN attempts` when the guess is right
+//! * `LOSE secret` when the guess used up the last attempt
+//! * `BYE` before the server closes the connection
+//! * `ERR message` for anything it could not act on
+
+use crate::{check_guess, Config, Game, Outcome};
+use rand::rngs::StdRng;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Guess(i64),
+    New,
+    Quit,
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Command::Guess(n) => write!(f, "GUESS {}", n),
+            Command::New => write!(f, "NEW"),
+            Command::Quit => write!(f, "QUIT"),
+        }
+    }
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Command, String> {
+        let mut words = line.split_whitespace();
+        let verb = words.next().unwrap_or("").to_ascii_uppercase();
+        let arg = words.next();
+
+        if words.next().is_some() {
+            return Err("too many arguments".to_string());
+        }
+
+        match (verb.as_str(), arg) {
+            ("GUESS", Some(n)) => n
+                .parse()
+                .map(Command::Guess)
+                .map_err(|_| format!("`{}` is not a number", n)),
+            ("GUESS", None) => Err("GUESS needs a number".to_string()),
+            ("NEW", None) => Ok(Command::New),
+            ("QUIT", None) => Ok(Command::Quit),
+            ("NEW" | "QUIT", Some(_)) => Err(format!("{} takes no arguments", verb)),
+            ("", _) => Err("empty command".to_string()),
+            _ => Err(format!("unknown command `{}`", verb)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reply {
+    Ready { min: i64, max: i64 },
+    Less,
+    Greater,
+    Win(u32),
+    Lose(i64),
+    Bye,
+    Err(String),
+}
+
+impl fmt::Display for Reply {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Reply::Ready { min, max } => write!(f, "READY {} {}", min, max),
+            Reply::Less => write!(f, "LESS"),
+            Reply::Greater => write!(f, "GREATER"),
+            Reply::Win(attempts) => write!(f, "WIN {}", attempts),
+            Reply::Lose(secret) => write!(f, "LOSE {}", secret),
+            Reply::Bye => write!(f, "BYE"),
+            // Messages must stay on one line.
+            Reply::Err(message) => write!(f, "ERR {}", message.replace('\n', " ")),
+        }
+    }
+}
+
+impl FromStr for Reply {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Reply, String> {
+        let line = line.trim_end_matches(['\r', '\n']);
+        let (verb, rest) = line.split_once(' ').unwrap_or((line, ""));
+        let number = |text: &str| text.parse().map_err(|_| format!("bad reply `{}`", line));
+
+        match verb {
+            "READY" => {
+                let (min, max) = rest
+                    .split_once(' ')
+                    .ok_or_else(|| format!("bad reply `{}`", line))?;
+                Ok(Reply::Ready {
+                    min: number(min)?,
+                    max: number(max)?,
+                })
+            }
+            "LESS" => Ok(Reply::Less),
+            "GREATER" => Ok(Reply::Greater),
+            "WIN" => rest
+                .parse()
+                .map(Reply::Win)
+                .map_err(|_| format!("bad reply `{}`", line)),
+            "LOSE" => Ok(Reply::Lose(number(rest)?)),
+            "BYE" => Ok(Reply::Bye),
+            "ERR" => Ok(Reply::Err(rest.to_string())),
+            _ => Err(format!("bad reply `{}`", line)),
+        }
+    }
+}
+
+/// One client's conversation with the server, independent of any socket.
+pub struct Session {
+    config: Config,
+    rng: StdRng,
+    game: Game,
+}
+
+impl Session {
+    pub fn new(config: Config, mut rng: StdRng) -> Session {
+        let game = Game::from_config(&config, &mut rng);
+        Session { config, rng, game }
+    }
+
+    /// The reply sent as soon as a client connects.
+    pub fn greeting(&self) -> Reply {
+        self.ready()
+    }
+
+    fn ready(&self) -> Reply {
+        Reply::Ready {
+            min: *self.game.range().start(),
+            max: *self.game.range().end(),
+        }
+    }
+
+    /// Answers one line from the client.
+    pub fn handle(&mut self, line: &str) -> Reply {
+        let command = match line.parse() {
+            Ok(command) => command,
+            Err(err) => return Reply::Err(err),
+        };
+
+        match command {
+            Command::Guess(n) => self.guess(n),
+            Command::New => {
+                self.game = Game::from_config(&self.config, &mut self.rng);
+                self.ready()
+            }
+            Command::Quit => Reply::Bye,
+        }
+    }
+
+    fn guess(&mut self, n: i64) -> Reply {
+        if self.game.is_over() {
+            return Reply::Err("game over; send NEW to play again".to_string());
+        }
+
+        let guess = match check_guess(n, self.game.range()) {
+            Ok(guess) => guess,
+            Err(err) => return Reply::Err(err.to_string()),
+        };
+
+        match self.game.guess(guess) {
+            Outcome::Win => Reply::Win(self.game.attempts()),
+            _ if self.game.is_lost() => Reply::Lose(self.game.secret()),
+            Outcome::TooSmall => Reply::Less,
+            Outcome::TooBig => Reply::Greater,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seeded_rng;
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!("GUESS 42".parse(), Ok(Command::Guess(42)));
+        assert_eq!("guess -7\r\n".parse(), Ok(Command::Guess(-7)));
+        assert_eq!("NEW".parse(), Ok(Command::New));
+        assert_eq!(" quit ".parse(), Ok(Command::Quit));
+        assert!("GUESS".parse::<Command>().is_err());
+        assert!("GUESS x".parse::<Command>().is_err());
+        assert!("NEW 3".parse::<Command>().is_err());
+        assert!("JUMP".parse::<Command>().is_err());
+        assert!("".parse::<Command>().is_err());
+    }
+
+    #[test]
+    fn replies_round_trip() {
+        let replies = [
+            Reply::Ready { min: -5, max: 5 },
+            Reply::Less,
+            Reply::Greater,
+            Reply::Win(7),
+            Reply::Lose(42),
+            Reply::Bye,
+            Reply::Err("out of range".to_string()),
+        ];
+
+        for reply in replies {
+            assert_eq!(reply.to_string().parse(), Ok(reply));
+        }
+    }
+
+    #[test]
+    fn session_plays_a_game() {
+        let config = Config {
+            max_attempts: Some(2),
+            ..Config::default()
+        };
+        let mut session = Session::new(config, seeded_rng(1));
+        let secret = session.game.secret();
+        let (wrong, direction) = if secret == 1 {
+            (2, Reply::Greater)
+        } else {
+            (1, Reply::Less)
+        };
+
+        assert_eq!(session.greeting(), Reply::Ready { min: 1, max: 100 });
+        assert_eq!(session.handle(&format!("GUESS {}", wrong)), direction);
+        assert!(matches!(session.handle("GUESS 500"), Reply::Err(_)));
+        assert_eq!(
+            session.handle(&format!("GUESS {}", wrong)),
+            Reply::Lose(secret)
+        );
+        assert!(matches!(session.handle("GUESS 5"), Reply::Err(_)));
+
+        assert_eq!(session.handle("NEW"), Reply::Ready { min: 1, max: 100 });
+        let secret = session.game.secret();
+        assert_eq!(session.handle(&format!("GUESS {}", secret)), Reply::Win(1));
+        assert_eq!(session.handle("QUIT"), Reply::Bye);
+    }
+}
@@ -0,0 +1,414 @@
+//! Replay files: everything needed to watch a game again or check it.
+//!
+//! A replay is plain text. The first line names the format version, then
+//! come `key value` settings and one `guess` line per guess:
+//!
+//! ```text
+//! guessing_game replay v1
+//! seed 7
+//! range 1 100
+//! max_guesses 10
+//! evil no
+//! guess 812 50 Greater
+//! guess 1630 25 Less
+//! ```
+//!
+//! A guess line holds the milliseconds since the game started, the guess
+//! and `guess.cmp(&secret)`. Readers skip keys they do not know and fall
+//! back to defaults for optional keys they do, so files written by older
+//! versions keep loading; only a newer format version is refused.
+
+use crate::date::Date;
+use crate::storage;
+use crate::transcript::GuessRecord;
+use crate::{seeded_rng, Config, Game};
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const MAGIC: &str = "guessing_game replay v";
+
+/// The format version this build writes, and the newest it can read.
+pub const VERSION: u32 = 1;
+
+/// How many replays the data directory keeps; older ones are deleted.
+pub const MAX_SAVED: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replay {
+    pub seed: u64,
+    pub range: RangeInclusive<i64>,
+    pub max_attempts: Option<u32>,
+    pub evil: bool,
+    pub guesses: Vec<GuessRecord>,
+}
+
+impl Replay {
+    /// Starts an empty recording of a game set up from `config` and `seed`.
+    pub fn new(config: &Config, seed: u64) -> Replay {
+        Replay {
+            seed,
+            range: config.range.clone(),
+            max_attempts: config.max_attempts,
+            evil: config.evil,
+            guesses: Vec::new(),
+        }
+    }
+
+    /// Where a replay of a game with `seed` played on `date` is saved
+    /// unless `--record` says otherwise.
+    ///
+    /// The name is `{date}-seed-{seed}.replay`. Playing the same seed again
+    /// that day adds `-2`, `-3` and so on, so no earlier replay is replaced.
+    pub fn default_path(date: Date, seed: u64) -> Option<PathBuf> {
+        let dir = storage::data_dir()?.join("replays");
+        let base = format!("{}-seed-{}", date, seed);
+
+        (1..)
+            .map(|n| match n {
+                1 => dir.join(format!("{}.replay", base)),
+                n => dir.join(format!("{}-{}.replay", base, n)),
+            })
+            .find(|path| !path.exists())
+    }
+
+    /// Deletes all but the newest `keep` replay files in `dir`, going by
+    /// when they were last written. Returns how many were deleted.
+    pub fn prune(dir: &Path, keep: usize) -> io::Result<usize> {
+        let mut files = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "replay") {
+                files.push((entry.metadata()?.modified()?, path));
+            }
+        }
+
+        // Newest first; names settle ties so the result does not depend
+        // on the order the directory lists them in.
+        files.sort_by(|a, b| b.cmp(a));
+        let old = files.get(keep..).unwrap_or_default();
+        for (_, path) in old {
+            fs::remove_file(path)?;
+        }
+        Ok(old.len())
+    }
+
+    pub fn push(&mut self, record: GuessRecord) {
+        self.guesses.push(record);
+    }
+
+    /// A config that sets up the same game again.
+    pub fn config(&self) -> Config {
+        Config {
+            range: self.range.clone(),
+            max_attempts: self.max_attempts,
+            seed: Some(self.seed),
+            evil: self.evil,
+            ..Config::default()
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "{}{}\nseed {}\nrange {} {}\n",
+            MAGIC,
+            VERSION,
+            self.seed,
+            self.range.start(),
+            self.range.end()
+        );
+        if let Some(max_attempts) = self.max_attempts {
+            text.push_str(&format!("max_guesses {}\n", max_attempts));
+        }
+        text.push_str(if self.evil { "evil yes\n" } else { "evil no\n" });
+        for record in &self.guesses {
+            text.push_str(&format!(
+                "guess {} {} {:?}\n",
+                record.elapsed.as_millis(),
+                record.guess,
+                record.ordering
+            ));
+        }
+        text
+    }
+
+    pub fn parse(text: &str) -> Result<Replay, ReplayError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()));
+
+        let version = lines
+            .next()
+            .and_then(|(_, line)| line.strip_prefix(MAGIC))
+            .and_then(|version| version.parse().ok())
+            .ok_or(ReplayError::NotAReplay)?;
+        if version > VERSION {
+            return Err(ReplayError::UnsupportedVersion(version));
+        }
+
+        let mut seed = None;
+        let mut range = None;
+        let mut replay = Replay {
+            seed: 0,
+            range: 1..=100,
+            max_attempts: None,
+            evil: false,
+            guesses: Vec::new(),
+        };
+
+        for (number, line) in lines.filter(|(_, line)| !line.is_empty()) {
+            let bad = || ReplayError::BadLine {
+                number,
+                line: line.to_string(),
+            };
+            let words: Vec<&str> = line.split_whitespace().collect();
+
+            match words.as_slice() {
+                ["seed", n] => seed = Some(n.parse().map_err(|_| bad())?),
+                ["range", min, max] => {
+                    let min: i64 = min.parse().map_err(|_| bad())?;
+                    let max: i64 = max.parse().map_err(|_| bad())?;
+                    if min > max {
+                        return Err(bad());
+                    }
+                    range = Some(min..=max);
+                }
+                ["max_guesses", n] => {
+                    replay.max_attempts = Some(n.parse().map_err(|_| bad())?);
+                }
+                ["evil", "yes"] => replay.evil = true,
+                ["evil", "no"] => replay.evil = false,
+                ["guess", ms, guess, ordering] => {
+                    let ordering = match *ordering {
+                        "Less" => Ordering::Less,
+                        "Greater" => Ordering::Greater,
+                        "Equal" => Ordering::Equal,
+                        _ => return Err(bad()),
+                    };
+                    replay.guesses.push(GuessRecord {
+                        guess: guess.parse().map_err(|_| bad())?,
+                        ordering,
+                        attempt: replay.guesses.len() as u32 + 1,
+                        elapsed: Duration::from_millis(ms.parse().map_err(|_| bad())?),
+                    });
+                }
+                ["seed" | "range" | "max_guesses" | "evil" | "guess", ..] => return Err(bad()),
+                // Written by a later version of this format.
+                _ => {}
+            }
+        }
+
+        replay.seed = seed.ok_or(ReplayError::Missing("seed"))?;
+        replay.range = range.ok_or(ReplayError::Missing("range"))?;
+        Ok(replay)
+    }
+
+    pub fn load(path: &Path) -> io::Result<Replay> {
+        let text = fs::read_to_string(path)?;
+        Replay::parse(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        storage::write_atomic(path, &self.to_text())
+    }
+
+    /// Plays the recorded guesses through a fresh game from the same seed
+    /// and checks that every answer is the one that was recorded.
+    pub fn verify(&self) -> Result<Game, VerifyError> {
+        let config = self.config();
+        let mut game = Game::from_config(&config, &mut seeded_rng(self.seed));
+
+        for (index, record) in self.guesses.iter().enumerate() {
+            if game.is_over() || !game.range().contains(&record.guess) {
+                return Err(VerifyError::Impossible {
+                    index,
+                    guess: record.guess,
+                });
+            }
+
+            let actual = game.guess(record.guess).ordering();
+            if actual != record.ordering {
+                return Err(VerifyError::Mismatch {
+                    index,
+                    guess: record.guess,
+                    recorded: record.ordering,
+                    actual,
+                });
+            }
+        }
+
+        Ok(game)
+    }
+}
+
+/// Why a replay file could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    NotAReplay,
+    UnsupportedVersion(u32),
+    Missing(&'static str),
+    BadLine { number: usize, line: String },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::NotAReplay => write!(f, "not a guessing_game replay"),
+            ReplayError::UnsupportedVersion(version) => write!(
+                f,
+                "replay format v{} is newer than this program understands (v{})",
+                version, VERSION
+            ),
+            ReplayError::Missing(key) => write!(f, "the replay has no `{}` line", key),
+            ReplayError::BadLine { number, line } => {
+                write!(f, "line {} is damaged: `{}`", number, line)
+            }
+        }
+    }
+}
+
+impl Error for ReplayError {}
+
+/// How a replay disagrees with the engine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyError {
+    /// Guess number `index` (from zero) was answered differently.
+    Mismatch {
+        index: usize,
+        guess: i64,
+        recorded: Ordering,
+        actual: Ordering,
+    },
+    /// Guess number `index` could not have been made: the game was already
+    /// over or the guess is outside the range.
+    Impossible { index: usize, guess: i64 },
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VerifyError::Mismatch {
+                index,
+                guess,
+                recorded,
+                actual,
+            } => write!(
+                f,
+                "guess #{} ({}) was recorded as {:?} but the engine says {:?}",
+                index + 1,
+                guess,
+                recorded,
+                actual
+            ),
+            VerifyError::Impossible { index, guess } => {
+                write!(
+                    f,
+                    "guess #{} ({}) could not have been made",
+                    index + 1,
+                    guess
+                )
+            }
+        }
+    }
+}
+
+impl Error for VerifyError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(guess: i64, ordering: Ordering, ms: u64) -> GuessRecord {
+        GuessRecord {
+            guess,
+            ordering,
+            attempt: 0,
+            elapsed: Duration::from_millis(ms),
+        }
+    }
+
+    fn recorded(seed: u64, guesses: &[i64]) -> Replay {
+        let config = Config::default();
+        let mut game = Game::from_config(&config, &mut seeded_rng(seed));
+        let mut replay = Replay::new(&config, seed);
+
+        for (i, &guess) in guesses.iter().enumerate() {
+            let ordering = game.guess(guess).ordering();
+            replay.push(GuessRecord {
+                attempt: i as u32 + 1,
+                ..record(guess, ordering, 500 * i as u64)
+            });
+        }
+        replay
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let mut replay = recorded(7, &[50, 25, 12]);
+        replay.max_attempts = Some(10);
+
+        assert_eq!(Replay::parse(&replay.to_text()), Ok(replay));
+    }
+
+    #[test]
+    fn reads_version_1_files() {
+        // Written by the first release; must keep loading unchanged.
+        let text = "guessing_game replay v1\nseed 7\nrange 1 100\nevil no\n\
+                    guess 812 50 Greater\nguess 1630 25 Less\n";
+        let replay = Replay::parse(text).unwrap();
+
+        assert_eq!(replay.seed, 7);
+        assert_eq!(replay.range, 1..=100);
+        assert_eq!(replay.max_attempts, None);
+        assert_eq!(replay.guesses[1].elapsed, Duration::from_millis(1630));
+        assert_eq!(replay.guesses[1].attempt, 2);
+    }
+
+    #[test]
+    fn skips_unknown_keys_but_refuses_newer_versions() {
+        let text = "guessing_game replay v1\nseed 1\nrange 1 10\ntheme dark\n";
+        assert!(Replay::parse(text).is_ok());
+
+        assert_eq!(
+            Replay::parse("guessing_game replay v2\nseed 1\nrange 1 10\n"),
+            Err(ReplayError::UnsupportedVersion(2))
+        );
+        assert_eq!(Replay::parse("seed 1\n"), Err(ReplayError::NotAReplay));
+        assert_eq!(
+            Replay::parse("guessing_game replay v1\nrange 1 10\n"),
+            Err(ReplayError::Missing("seed"))
+        );
+        assert!(matches!(
+            Replay::parse("guessing_game replay v1\nseed 1\nrange 1 10\nguess 5 x Less\n"),
+            Err(ReplayError::BadLine { number: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn verifies_against_the_engine() {
+        let secret = Game::from_config(&Config::default(), &mut seeded_rng(3)).secret();
+        let replay = recorded(3, &[50, secret]);
+        assert!(replay.verify().unwrap().is_won());
+
+        let mut forged = replay.clone();
+        forged.guesses[0].ordering = forged.guesses[0].ordering.reverse();
+        assert!(matches!(
+            forged.verify(),
+            Err(VerifyError::Mismatch { index: 0, .. })
+        ));
+
+        let mut extra = replay.clone();
+        extra.guesses.push(record(1, Ordering::Less, 9_000));
+        assert_eq!(
+            extra.verify().unwrap_err(),
+            VerifyError::Impossible { index: 2, guess: 1 }
+        );
+    }
+}
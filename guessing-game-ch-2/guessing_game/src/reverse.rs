@@ -0,0 +1,285 @@
+use crate::interval::Interval;
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// The player's reply to one of the computer's guesses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer {
+    /// The player's number is higher than the guess.
+    Higher,
+    /// The player's number is lower than the guess.
+    Lower,
+    Correct,
+}
+
+impl Answer {
+    /// How the guess compares to the player's number.
+    pub fn ordering(self) -> Ordering {
+        match self {
+            Answer::Higher => Ordering::Less,
+            Answer::Lower => Ordering::Greater,
+            Answer::Correct => Ordering::Equal,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Answer::Higher => "higher",
+            Answer::Lower => "lower",
+            Answer::Correct => "correct",
+        })
+    }
+}
+
+impl FromStr for Answer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Answer, String> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "h" | "higher" | "+" => Ok(Answer::Higher),
+            "l" | "lower" | "-" => Ok(Answer::Lower),
+            "c" | "correct" | "=" | "y" | "yes" => Ok(Answer::Correct),
+            _ => Err(format!(
+                "`{}` is not an answer; type higher, lower or correct.",
+                s.trim()
+            )),
+        }
+    }
+}
+
+/// An answer that cannot be true given what the player said before.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cheating {
+    /// Two answers that leave no number possible, in the order given.
+    Contradiction {
+        first: (i64, Answer),
+        second: (i64, Answer),
+    },
+    /// An answer that points past the edge of the agreed range.
+    OutsideRange {
+        guess: i64,
+        answer: Answer,
+        min: i64,
+        max: i64,
+    },
+}
+
+impl fmt::Display for Cheating {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Cheating::Contradiction { first, second } => write!(
+                f,
+                "Cheater! You said {} than {} and then {} than {}.",
+                first.1, first.0, second.1, second.0
+            ),
+            Cheating::OutsideRange {
+                guess,
+                answer,
+                min,
+                max,
+            } => write!(
+                f,
+                "Cheater! You said {} than {}, but your number is between {} and {}.",
+                answer, guess, min, max
+            ),
+        }
+    }
+}
+
+impl Error for Cheating {}
+
+/// The computer's side of a game where the player holds the secret.
+///
+/// It always guesses the middle of the numbers still possible, and names
+/// the number without asking once only one is left, so it needs at most
+/// `ceil(log2(n))` guesses for a range of `n` numbers.
+#[derive(Debug, Clone)]
+pub struct Reverser {
+    range: RangeInclusive<i64>,
+    candidates: Interval,
+    answers: Vec<(i64, Answer)>,
+    // Indexes into `answers` of the replies that set each current bound.
+    low_set_by: Option<usize>,
+    high_set_by: Option<usize>,
+}
+
+impl Reverser {
+    pub fn new(range: RangeInclusive<i64>) -> Reverser {
+        Reverser {
+            candidates: Interval::new(&range),
+            range,
+            answers: Vec::new(),
+            low_set_by: None,
+            high_set_by: None,
+        }
+    }
+
+    /// The player's number, once it is the only one left. That can be
+    /// before any guess, for a range holding a single number.
+    pub fn found(&self) -> Option<i64> {
+        if self.candidates.size() == 1 {
+            self.candidates.low()
+        } else {
+            None
+        }
+    }
+
+    /// The number the computer is asking about now.
+    pub fn guess(&self) -> i64 {
+        self.candidates.midpoint().expect("no candidates left")
+    }
+
+    /// Takes the player's answer to [`guess`](Reverser::guess).
+    ///
+    /// Returns the player's number once it is [found](Reverser::found). An
+    /// answer that contradicts an earlier one is rejected and leaves the
+    /// state as it was.
+    pub fn answer(&mut self, answer: Answer) -> Result<Option<i64>, Cheating> {
+        if self.found().is_some() {
+            panic!("The number has already been found.");
+        }
+
+        let guess = self.guess();
+        let narrowed = self.candidates.narrowed(guess, answer.ordering());
+
+        if narrowed.is_empty() {
+            let earlier = match answer {
+                Answer::Higher => self.high_set_by,
+                _ => self.low_set_by,
+            };
+            return Err(match earlier {
+                Some(index) => Cheating::Contradiction {
+                    first: self.answers[index],
+                    second: (guess, answer),
+                },
+                None => Cheating::OutsideRange {
+                    guess,
+                    answer,
+                    min: *self.range.start(),
+                    max: *self.range.end(),
+                },
+            });
+        }
+
+        let index = self.answers.len();
+        self.answers.push((guess, answer));
+        self.candidates = narrowed;
+
+        match answer {
+            Answer::Higher => self.low_set_by = Some(index),
+            Answer::Lower => self.high_set_by = Some(index),
+            Answer::Correct => {}
+        }
+
+        Ok(self.found())
+    }
+
+    /// Every guess so far with the answer it got.
+    pub fn answers(&self) -> &[(i64, Answer)] {
+        &self.answers
+    }
+
+    pub fn guesses(&self) -> u32 {
+        self.answers.len() as u32
+    }
+
+    pub fn candidates(&self) -> &Interval {
+        &self.candidates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(range: RangeInclusive<i64>, secret: i64) -> u32 {
+        let mut reverser = Reverser::new(range);
+        if let Some(found) = reverser.found() {
+            assert_eq!(found, secret);
+            return 0;
+        }
+        loop {
+            let answer = match secret.cmp(&reverser.guess()) {
+                Ordering::Greater => Answer::Higher,
+                Ordering::Less => Answer::Lower,
+                Ordering::Equal => Answer::Correct,
+            };
+            if let Some(found) = reverser.answer(answer).unwrap() {
+                assert_eq!(found, secret);
+                return reverser.guesses();
+            }
+        }
+    }
+
+    #[test]
+    fn finds_every_number_within_the_bound() {
+        for secret in 1..=100 {
+            assert!(solve(1..=100, secret) <= 7);
+        }
+        for secret in -500..=499 {
+            assert!(solve(-500..=499, secret) <= 10);
+        }
+        for secret in 1..=128 {
+            assert!(solve(1..=128, secret) <= 7);
+        }
+        assert_eq!(solve(1..=2, 2), 1);
+        assert_eq!(solve(7..=7, 7), 0);
+        for secret in [i64::MIN, -1, 0, 123_456_789, i64::MAX] {
+            assert!(solve(i64::MIN..=i64::MAX, secret) <= 64);
+        }
+    }
+
+    #[test]
+    fn catches_contradictions() {
+        let mut reverser = Reverser::new(1..=100);
+
+        assert_eq!(reverser.guess(), 50);
+        reverser.answer(Answer::Lower).unwrap();
+        assert_eq!(reverser.guess(), 25);
+        reverser.answer(Answer::Higher).unwrap();
+        assert_eq!(reverser.guess(), 37);
+        while reverser.candidates().size() > 2 {
+            reverser.answer(Answer::Lower).unwrap();
+        }
+
+        assert_eq!(reverser.guess(), 26);
+        let err = reverser.answer(Answer::Lower).unwrap_err();
+        assert_eq!(
+            err,
+            Cheating::Contradiction {
+                first: (25, Answer::Higher),
+                second: (26, Answer::Lower),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Cheater! You said higher than 25 and then lower than 26."
+        );
+        assert_eq!(reverser.found(), None);
+    }
+
+    #[test]
+    fn catches_answers_past_the_range() {
+        let mut reverser = Reverser::new(1..=2);
+
+        assert_eq!(reverser.guess(), 1);
+        assert!(matches!(
+            reverser.answer(Answer::Lower),
+            Err(Cheating::OutsideRange { guess: 1, .. })
+        ));
+        assert_eq!(reverser.guesses(), 0);
+    }
+
+    #[test]
+    fn parses_answers() {
+        assert_eq!("H".parse(), Ok(Answer::Higher));
+        assert_eq!(" lower\n".parse(), Ok(Answer::Lower));
+        assert_eq!("=".parse(), Ok(Answer::Correct));
+        assert!("maybe".parse::<Answer>().is_err());
+    }
+}
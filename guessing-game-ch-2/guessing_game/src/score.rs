@@ -0,0 +1,66 @@
+use std::ops::RangeInclusive;
+
+/// The best score a game can earn.
+pub const MAX_SCORE: u32 = 100;
+
+/// How many numbers `range` holds, without overflowing on `i64` extremes.
+pub fn range_size(range: &RangeInclusive<i64>) -> u128 {
+    if range.is_empty() {
+        0
+    } else {
+        (*range.end() as i128 - *range.start() as i128 + 1) as u128
+    }
+}
+
+/// The number of guesses a perfect bisection needs to win,
+/// `ceil(log2(size + 1))`.
+///
+/// Every guess either wins or rules out the number guessed as well as one
+/// side of it, so `k` guesses can tell apart at most `2^k - 1` numbers.
+pub fn optimal_guesses(range: &RangeInclusive<i64>) -> u32 {
+    let size = range_size(range).max(1);
+    128 - size.leading_zeros()
+}
+
+/// Scores a finished game out of [`MAX_SCORE`].
+///
+/// Winning in `optimal` guesses or fewer earns full marks; every extra
+/// guess lowers the score in proportion. A lost game scores zero.
+pub fn score(won: bool, guesses: u32, optimal: u32) -> u32 {
+    if !won || guesses == 0 {
+        return 0;
+    }
+
+    if guesses <= optimal {
+        MAX_SCORE
+    } else {
+        MAX_SCORE * optimal / guesses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn optimal_is_ceil_log2_of_size_plus_one() {
+        assert_eq!(optimal_guesses(&(1..=1)), 1);
+        assert_eq!(optimal_guesses(&(1..=2)), 2);
+        assert_eq!(optimal_guesses(&(1..=3)), 2);
+        assert_eq!(optimal_guesses(&(1..=10)), 4);
+        assert_eq!(optimal_guesses(&(1..=100)), 7);
+        assert_eq!(optimal_guesses(&(1..=127)), 7);
+        assert_eq!(optimal_guesses(&(1..=128)), 8);
+        assert_eq!(optimal_guesses(&(1..=1_000_000)), 20);
+        assert_eq!(optimal_guesses(&(i64::MIN..=i64::MAX)), 65);
+    }
+
+    #[test]
+    fn score_scales_with_extra_guesses() {
+        assert_eq!(score(true, 3, 7), 100);
+        assert_eq!(score(true, 7, 7), 100);
+        assert_eq!(score(true, 14, 7), 50);
+        assert_eq!(score(true, 70, 7), 10);
+        assert_eq!(score(false, 7, 7), 0);
+    }
+}
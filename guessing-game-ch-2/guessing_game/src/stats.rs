@@ -0,0 +1,257 @@
+use crate::storage;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const HEADER: &str = "# guessing_game stats v1";
+
+/// Lifetime totals across every game played on this machine.
+///
+/// Only single-player number games count: regular games and today's
+/// daily puzzle. Party games would mix several players into one record,
+/// and the word, bulls-and-cows and reverse games take a different number
+/// of guesses, so none of those are recorded. Scripted runs are not
+/// recorded either.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub games: u32,
+    pub wins: u32,
+    /// How many games were won with each number of guesses.
+    pub distribution: BTreeMap<u32, u32>,
+    /// Wins in a row, up to and including the last game.
+    pub current_streak: u32,
+    pub best_streak: u32,
+}
+
+impl Stats {
+    /// Where the stats live by default, inside [`storage::data_dir`].
+    pub fn default_path() -> Option<PathBuf> {
+        storage::data_dir().map(|dir| dir.join("stats.tsv"))
+    }
+
+    /// Parses saved stats. Unknown or damaged lines are skipped, so a
+    /// newer file still loads everything this version understands.
+    pub fn parse(text: &str) -> Stats {
+        let mut stats = Stats::default();
+
+        for line in text.lines().filter(|line| !line.starts_with('#')) {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let numbers: Option<Vec<u32>> = fields[1..].iter().map(|n| n.parse().ok()).collect();
+
+            match (fields[0], numbers.as_deref()) {
+                ("games", Some(&[n])) => stats.games = n,
+                ("wins", Some(&[n])) => stats.wins = n,
+                ("current_streak", Some(&[n])) => stats.current_streak = n,
+                ("best_streak", Some(&[n])) => stats.best_streak = n,
+                ("won_in", Some(&[guesses, count])) => {
+                    stats.distribution.insert(guesses, count);
+                }
+                _ => {}
+            }
+        }
+
+        stats
+    }
+
+    /// Loads the stats at `path`; a missing file means no games yet.
+    pub fn load(path: &Path) -> io::Result<Stats> {
+        storage::read_or_empty(path).map(|text| Stats::parse(&text))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        storage::write_atomic(path, &self.to_text())
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "{}\ngames\t{}\nwins\t{}\ncurrent_streak\t{}\nbest_streak\t{}\n",
+            HEADER, self.games, self.wins, self.current_streak, self.best_streak
+        );
+        for (guesses, count) in &self.distribution {
+            text.push_str(&format!("won_in\t{}\t{}\n", guesses, count));
+        }
+        text
+    }
+
+    /// Adds one finished game.
+    ///
+    /// The counts come from a file that may have been edited by hand, so
+    /// they stop at `u32::MAX` instead of overflowing.
+    pub fn record(&mut self, won: bool, guesses: u32) {
+        self.games = self.games.saturating_add(1);
+
+        if won {
+            self.wins = self.wins.saturating_add(1);
+            let count = self.distribution.entry(guesses).or_insert(0);
+            *count = count.saturating_add(1);
+            self.current_streak = self.current_streak.saturating_add(1);
+            self.best_streak = self.best_streak.max(self.current_streak);
+        } else {
+            self.current_streak = 0;
+        }
+    }
+
+    /// Folds in stats kept somewhere else, such as on another machine.
+    ///
+    /// Totals add up. The games cannot be put back in order, so the
+    /// streaks are the longer of the two rather than a joined streak.
+    pub fn merge(&mut self, other: &Stats) {
+        self.games = self.games.saturating_add(other.games);
+        self.wins = self.wins.saturating_add(other.wins);
+        for (&guesses, &count) in &other.distribution {
+            let total = self.distribution.entry(guesses).or_insert(0);
+            *total = total.saturating_add(count);
+        }
+        self.current_streak = self.current_streak.max(other.current_streak);
+        self.best_streak = self.best_streak.max(other.best_streak);
+    }
+
+    /// The share of games won, from 0 to 1, or `None` before any game.
+    pub fn win_rate(&self) -> Option<f64> {
+        (self.games > 0).then(|| f64::from(self.wins) / f64::from(self.games))
+    }
+
+    /// Mean guesses over the games that were won.
+    pub fn mean_guesses(&self) -> Option<f64> {
+        let won = self.won();
+        let total: u64 = self
+            .distribution
+            .iter()
+            .map(|(&guesses, &count)| u64::from(guesses) * u64::from(count))
+            .sum();
+
+        (won > 0).then(|| total as f64 / won as f64)
+    }
+
+    /// Median guesses over the games that were won; with an even number
+    /// of games it is halfway between the middle two.
+    pub fn median_guesses(&self) -> Option<f64> {
+        let won = self.won();
+        if won == 0 {
+            return None;
+        }
+
+        // The `n`th smallest guess count, counting from zero.
+        let nth = |n: u64| {
+            let mut seen = 0;
+            for (&guesses, &count) in &self.distribution {
+                seen += u64::from(count);
+                if n < seen {
+                    return guesses;
+                }
+            }
+            unreachable!("n is less than the number of wins")
+        };
+
+        let low = nth((won - 1) / 2);
+        let high = nth(won / 2);
+        Some((f64::from(low) + f64::from(high)) / 2.0)
+    }
+
+    /// How many games the distribution holds, which can be more than a
+    /// `u32` once files from several machines are merged.
+    fn won(&self) -> u64 {
+        self.distribution.values().copied().map(u64::from).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn played(games: &[Option<u32>]) -> Stats {
+        let mut stats = Stats::default();
+        for game in games {
+            match game {
+                Some(guesses) => stats.record(true, *guesses),
+                None => stats.record(false, 10),
+            }
+        }
+        stats
+    }
+
+    #[test]
+    fn records_games_and_streaks() {
+        let stats = played(&[
+            Some(4),
+            Some(6),
+            None,
+            Some(5),
+            Some(7),
+            Some(5),
+            None,
+            Some(3),
+        ]);
+
+        assert_eq!(stats.games, 8);
+        assert_eq!(stats.wins, 6);
+        assert_eq!(stats.current_streak, 1);
+        assert_eq!(stats.best_streak, 3);
+        assert_eq!(stats.distribution[&5], 2);
+        assert_eq!(stats.win_rate(), Some(0.75));
+        assert_eq!(stats.mean_guesses(), Some(5.0));
+        assert_eq!(stats.median_guesses(), Some(5.0));
+    }
+
+    #[test]
+    fn median_of_an_even_count_is_halfway() {
+        assert_eq!(played(&[Some(3), Some(8)]).median_guesses(), Some(5.5));
+        assert_eq!(played(&[Some(3)]).median_guesses(), Some(3.0));
+    }
+
+    #[test]
+    fn empty_stats_have_no_averages() {
+        let stats = played(&[None]);
+
+        assert_eq!(stats.win_rate(), Some(0.0));
+        assert_eq!(stats.mean_guesses(), None);
+        assert_eq!(stats.median_guesses(), None);
+        assert_eq!(Stats::default().win_rate(), None);
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let stats = played(&[Some(4), None, Some(6), Some(6)]);
+
+        assert_eq!(Stats::parse(&stats.to_text()), stats);
+        assert_eq!(
+            Stats::parse("games\t3\nwins\tx\nfuture_field\t1\nwon_in\t5\n"),
+            Stats {
+                games: 3,
+                ..Stats::default()
+            }
+        );
+    }
+
+    #[test]
+    fn huge_counts_stop_at_the_maximum() {
+        let text = format!(
+            "games\t{max}\nwins\t{max}\ncurrent_streak\t{max}\nbest_streak\t{max}\n\
+             won_in\t3\t{max}\nwon_in\t4\t{max}\n",
+            max = u32::MAX
+        );
+        let mut stats = Stats::parse(&text);
+        stats.record(true, 3);
+        stats.merge(&stats.clone());
+
+        assert_eq!(stats.games, u32::MAX);
+        assert_eq!(stats.wins, u32::MAX);
+        assert_eq!(stats.current_streak, u32::MAX);
+        assert_eq!(stats.distribution[&3], u32::MAX);
+        assert_eq!(stats.mean_guesses(), Some(3.5));
+        assert_eq!(stats.median_guesses(), Some(3.5));
+    }
+
+    #[test]
+    fn merges_two_machines() {
+        let mut home = played(&[Some(4), Some(5), None]);
+        let work = played(&[Some(5), Some(6), Some(7)]);
+        home.merge(&work);
+
+        assert_eq!(home.games, 6);
+        assert_eq!(home.wins, 5);
+        assert_eq!(home.distribution[&5], 2);
+        assert_eq!(home.current_streak, 3);
+        assert_eq!(home.best_streak, 3);
+    }
+}
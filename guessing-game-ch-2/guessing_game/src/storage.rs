@@ -0,0 +1,122 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Environment variable that overrides where game data is kept.
+pub const DATA_DIR_VAR: &str = "GUESSING_GAME_DATA_DIR";
+
+/// The directory holding high scores and other saved data.
+///
+/// Uses `$GUESSING_GAME_DATA_DIR` when set, then the platform's usual
+/// per-user data directory. Returns `None` if neither can be found.
+pub fn data_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os(DATA_DIR_VAR) {
+        return Some(PathBuf::from(dir));
+    }
+
+    let base = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+    };
+
+    base.map(|base| base.join("guessing_game"))
+}
+
+/// Replaces the file at `path` with `contents`.
+///
+/// The data is written to a temporary file next to `path` and then renamed
+/// over it, so a crash midway never leaves a half-written file behind.
+/// Each write gets its own temporary file, named after the process and a
+/// counter, so two games saving at once cannot mix up their data.
+pub fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    static WRITES: AtomicU64 = AtomicU64::new(0);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(
+        ".{}-{}.tmp",
+        process::id(),
+        WRITES.fetch_add(1, Ordering::Relaxed)
+    ));
+    let tmp = PathBuf::from(tmp);
+
+    let written = fs::write(&tmp, contents).and_then(|()| fs::rename(&tmp, path));
+    if written.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    written
+}
+
+/// `field` made safe for a tab-separated line: tabs and newlines would
+/// break the format, so they become spaces.
+pub fn clean(field: &str) -> String {
+    field.replace(['\t', '\n', '\r'], " ")
+}
+
+/// Reads `path` as text, treating a missing file as empty.
+pub fn read_or_empty(path: &Path) -> io::Result<String> {
+    match fs::read(path) {
+        Ok(bytes) => Ok(String::from_utf8_lossy(&bytes).into_owned()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn concurrent_writes_never_mix() {
+        let dir = env::temp_dir().join(format!("guessing_game-storage-{}", process::id()));
+        let path = dir.join("shared.txt");
+
+        let writers: Vec<_> = (0..8)
+            .map(|n| {
+                let path = path.clone();
+                thread::spawn(move || {
+                    for _ in 0..20 {
+                        write_atomic(&path, &n.to_string().repeat(10_000)).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let text = fs::read_to_string(&path).unwrap();
+        let left: Vec<_> = fs::read_dir(&dir).unwrap().collect();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(text.len(), 10_000);
+        assert_eq!(text, text[..1].repeat(10_000));
+        assert_eq!(left.len(), 1, "temporary files were left behind");
+    }
+
+    #[test]
+    fn failed_writes_clean_up() {
+        let dir = env::temp_dir().join(format!("guessing_game-storage-fail-{}", process::id()));
+        let target = dir.join("taken");
+        fs::create_dir_all(target.join("not empty")).unwrap();
+
+        let written = write_atomic(&target, "data");
+        let left = fs::read_dir(&dir).unwrap().count();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(written.is_err());
+        assert_eq!(left, 1, "the temporary file was left behind");
+    }
+}
@@ -0,0 +1,271 @@
+use crate::interval::Interval;
+use crate::{score, seeded_rng, Config, Game};
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::collections::BTreeMap;
+
+/// Something that can play the guessing game on its own.
+///
+/// The driver keeps the [`Interval`] of numbers that are still possible,
+/// narrowing it with every `Ordering` the game reports, and asks the
+/// strategy for a guess from it. The interval is never empty when passed.
+pub trait GuessStrategy {
+    fn name(&self) -> &str;
+
+    fn next_guess(&mut self, candidates: &Interval) -> i64;
+}
+
+/// Always guesses the middle of what is left.
+#[derive(Debug, Default)]
+pub struct BinarySearch;
+
+impl GuessStrategy for BinarySearch {
+    fn name(&self) -> &str {
+        "binary"
+    }
+
+    fn next_guess(&mut self, candidates: &Interval) -> i64 {
+        candidates.midpoint().expect("no candidates left")
+    }
+}
+
+/// Guesses any of the remaining candidates at random.
+#[derive(Debug)]
+pub struct RandomGuess {
+    rng: StdRng,
+}
+
+impl RandomGuess {
+    pub fn new(seed: u64) -> RandomGuess {
+        // Mixed so that sharing a seed with the secrets does not make the
+        // guesses replay the secrets.
+        RandomGuess {
+            rng: seeded_rng(seed ^ 0x9e37_79b9_7f4a_7c15),
+        }
+    }
+}
+
+impl GuessStrategy for RandomGuess {
+    fn name(&self) -> &str {
+        "random"
+    }
+
+    fn next_guess(&mut self, candidates: &Interval) -> i64 {
+        let (low, high) = candidates
+            .low()
+            .zip(candidates.high())
+            .expect("no candidates left");
+        self.rng.gen_range(low..=high)
+    }
+}
+
+/// Counts up from the lowest candidate.
+#[derive(Debug, Default)]
+pub struct LinearScan;
+
+impl GuessStrategy for LinearScan {
+    fn name(&self) -> &str {
+        "linear"
+    }
+
+    fn next_guess(&mut self, candidates: &Interval) -> i64 {
+        candidates.low().expect("no candidates left")
+    }
+}
+
+/// The names accepted by [`strategy_by_name`].
+pub const STRATEGY_NAMES: [&str; 3] = ["binary", "random", "linear"];
+
+/// Builds a strategy from its name; `seed` is used by the random one.
+pub fn strategy_by_name(name: &str, seed: u64) -> Option<Box<dyn GuessStrategy>> {
+    match name {
+        "binary" => Some(Box::new(BinarySearch)),
+        "random" => Some(Box::new(RandomGuess::new(seed))),
+        "linear" => Some(Box::new(LinearScan)),
+        _ => None,
+    }
+}
+
+/// Lets `strategy` play `game` until it is over.
+pub fn auto_play(game: &mut Game, strategy: &mut dyn GuessStrategy) {
+    let mut candidates = Interval::new(game.range());
+
+    while !game.is_over() {
+        let guess = strategy.next_guess(&candidates);
+        let outcome = game.guess(guess);
+        candidates.narrow(guess, outcome.ordering());
+    }
+}
+
+/// How a strategy fared over many games.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Summary {
+    pub games: u32,
+    pub wins: u32,
+    pub total_guesses: u64,
+    pub total_score: u64,
+    pub max_guesses: u32,
+    /// How many games took each number of guesses.
+    pub distribution: BTreeMap<u32, u32>,
+}
+
+impl Summary {
+    pub fn add(&mut self, game: &Game) {
+        let guesses = game.attempts();
+
+        self.games += 1;
+        self.wins += u32::from(game.is_won());
+        self.total_guesses += u64::from(guesses);
+        self.total_score += u64::from(game.score());
+        self.max_guesses = self.max_guesses.max(guesses);
+        *self.distribution.entry(guesses).or_insert(0) += 1;
+    }
+
+    pub fn mean_guesses(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.total_guesses as f64 / f64::from(self.games)
+        }
+    }
+
+    pub fn mean_score(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.total_score as f64 / f64::from(self.games)
+        }
+    }
+}
+
+/// Plays `games` games set up by `config` with `strategy`.
+///
+/// The secrets come from `seed`, so every strategy run with the same seed
+/// faces exactly the same secrets. Without a limit in `config`, each game
+/// gets as many guesses as bisection needs, so a slow strategy on a huge
+/// range loses instead of running for billions of guesses.
+pub fn simulate(
+    strategy: &mut dyn GuessStrategy,
+    config: &Config,
+    games: u32,
+    seed: u64,
+) -> Summary {
+    let mut rng = seeded_rng(seed);
+    let mut summary = Summary::default();
+    let max_attempts = config
+        .max_attempts
+        .unwrap_or_else(|| score::optimal_guesses(&config.range));
+
+    for _ in 0..games {
+        let mut game = Game::from_config(config, &mut rng).with_max_attempts(Some(max_attempts));
+        auto_play(&mut game, strategy);
+        summary.add(&game);
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ops::RangeInclusive;
+
+    fn config(range: RangeInclusive<i64>, max_attempts: Option<u32>) -> Config {
+        Config {
+            range,
+            max_attempts,
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn binary_search_is_optimal() {
+        let summary = simulate(&mut BinarySearch, &config(1..=100, None), 1000, 1);
+
+        assert_eq!(summary.wins, 1000);
+        assert!(summary.max_guesses <= 7);
+        assert_eq!(summary.mean_score(), 100.0);
+
+        // Powers of two need the extra guess too, and so does a host that
+        // always picks the larger side.
+        for range in [1..=2, 1..=128] {
+            let summary = simulate(&mut BinarySearch, &config(range, None), 200, 3);
+            assert_eq!(summary.mean_score(), 100.0);
+        }
+        let evil = Config {
+            evil: true,
+            ..config(1..=1024, None)
+        };
+        assert_eq!(
+            simulate(&mut BinarySearch, &evil, 20, 4).mean_score(),
+            100.0
+        );
+    }
+
+    #[test]
+    fn every_strategy_wins_with_a_guess_per_number() {
+        for name in STRATEGY_NAMES {
+            let mut strategy = strategy_by_name(name, 9).unwrap();
+            let summary = simulate(strategy.as_mut(), &config(-20..=20, Some(41)), 200, 2);
+
+            assert_eq!(summary.wins, 200, "{} lost a game", name);
+            assert_eq!(summary.distribution.values().sum::<u32>(), 200);
+        }
+    }
+
+    #[test]
+    fn unlimited_games_stop_at_the_bisection_bound() {
+        let summary = simulate(&mut LinearScan, &config(0..=i64::MAX, None), 20, 6);
+
+        assert_eq!(summary.max_guesses, 64);
+        assert_eq!(summary.wins, 0);
+
+        let summary = simulate(&mut LinearScan, &config(1..=100, None), 200, 6);
+        assert_eq!(summary.max_guesses, 7);
+    }
+
+    #[test]
+    fn linear_scan_takes_secret_minus_low_plus_one() {
+        let mut game = Game::with_secret(1..=100, 37);
+        auto_play(&mut game, &mut LinearScan);
+
+        assert_eq!(game.attempts(), 37);
+    }
+
+    #[test]
+    fn same_seed_same_summary() {
+        let run = || {
+            simulate(
+                &mut RandomGuess::new(5),
+                &config(1..=1000, Some(10)),
+                300,
+                5,
+            )
+        };
+
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn random_guesses_do_not_mirror_secrets() {
+        let summary = simulate(&mut RandomGuess::new(5), &config(1..=1000, None), 100, 5);
+
+        assert!(summary.max_guesses > 1);
+    }
+
+    #[test]
+    fn evil_host_makes_every_binary_game_worst_case() {
+        let config = Config {
+            evil: true,
+            ..config(1..=1000, None)
+        };
+        let summary = simulate(&mut BinarySearch, &config, 20, 1);
+
+        assert_eq!(summary.distribution.get(&10), Some(&20));
+    }
+
+    #[test]
+    fn unknown_strategy() {
+        assert!(strategy_by_name("psychic", 0).is_none());
+    }
+}
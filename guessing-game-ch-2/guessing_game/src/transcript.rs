@@ -0,0 +1,48 @@
+use std::cmp::Ordering;
+use std::time::Duration;
+
+/// One guess as it happened, for machine-readable transcripts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GuessRecord {
+    pub guess: i64,
+    /// `guess.cmp(&secret)`.
+    pub ordering: Ordering,
+    /// Which guess this was, counting from one.
+    pub attempt: u32,
+    /// Time since the game started.
+    pub elapsed: Duration,
+}
+
+impl GuessRecord {
+    /// Formats the record as a single-line JSON object, for example
+    /// `{"guess":50,"result":"Less","attempt":1,"elapsed_ms":812}`.
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"guess\":{},\"result\":\"{:?}\",\"attempt\":{},\"elapsed_ms\":{}}}",
+            self.guess,
+            self.ordering,
+            self.attempt,
+            self.elapsed.as_millis()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_json() {
+        let record = GuessRecord {
+            guess: -12,
+            ordering: Ordering::Greater,
+            attempt: 3,
+            elapsed: Duration::from_micros(1_500_900),
+        };
+
+        assert_eq!(
+            record.to_json(),
+            r#"{"guess":-12,"result":"Greater","attempt":3,"elapsed_ms":1500}"#
+        );
+    }
+}
@@ -0,0 +1,342 @@
+//! The word game: guess a secret word and get feedback on every letter.
+//!
+//! Words come from a plain text file with one word per line. Blank lines,
+//! `#` comments and anything that is not made of ASCII letters are skipped.
+//! A list of common five-letter words is built in for players without one.
+
+use crate::storage::data_dir;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The word list used when the player has not supplied one.
+const BUILTIN: &str = include_str!("words.txt");
+
+/// Guesses allowed when the player does not ask for a different number.
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 6;
+
+/// Wins within this many guesses get a full score.
+pub const PAR: u32 = 3;
+
+/// What a guessed letter says about the secret word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mark {
+    /// The letter is in the secret at this position.
+    Correct,
+    /// The letter is in the secret, but somewhere else.
+    Present,
+    /// The letter is not in the secret, or every copy is already marked.
+    Absent,
+}
+
+impl Mark {
+    pub fn symbol(self) -> char {
+        match self {
+            Mark::Correct => '=',
+            Mark::Present => '+',
+            Mark::Absent => '-',
+        }
+    }
+}
+
+/// Marks every letter of `guess` against `secret`; both are lowercase
+/// ASCII words of the same length.
+///
+/// A letter is only marked present as many times as it appears in the
+/// secret and is not already correct, so guessing `eerie` against `crepe`
+/// marks the first `e` present, the second absent and the last correct.
+pub fn mark(secret: &str, guess: &str) -> Vec<Mark> {
+    let secret = secret.as_bytes();
+    let guess = guess.as_bytes();
+    let mut marks = vec![Mark::Absent; guess.len()];
+    let mut unmatched = [0u8; 26];
+
+    for (i, (&s, &g)) in secret.iter().zip(guess).enumerate() {
+        if s == g {
+            marks[i] = Mark::Correct;
+        } else {
+            unmatched[usize::from(s - b'a')] += 1;
+        }
+    }
+
+    for (i, &g) in guess.iter().enumerate() {
+        let left = &mut unmatched[usize::from(g - b'a')];
+        if marks[i] == Mark::Absent && *left > 0 {
+            marks[i] = Mark::Present;
+            *left -= 1;
+        }
+    }
+
+    marks
+}
+
+/// Why a guess was not accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordError {
+    WrongLength { expected: usize, found: usize },
+    NotInList(String),
+}
+
+impl fmt::Display for WordError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WordError::WrongLength { expected, found } => write!(
+                f,
+                "The word has {} letters, but you typed {}.",
+                expected, found
+            ),
+            WordError::NotInList(word) => write!(f, "`{}` is not in the word list.", word),
+        }
+    }
+}
+
+impl Error for WordError {}
+
+/// The words a game may use, sorted and without duplicates.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WordList {
+    words: Vec<String>,
+}
+
+impl WordList {
+    /// Where the player's own word list lives unless `--words` says
+    /// otherwise. Without one, games use [`WordList::builtin`].
+    pub fn default_path() -> Option<PathBuf> {
+        data_dir().map(|dir| dir.join("words.txt"))
+    }
+
+    /// The list that ships with the game.
+    pub fn builtin() -> WordList {
+        WordList::parse(BUILTIN)
+    }
+
+    pub fn parse(text: &str) -> WordList {
+        let mut words: Vec<String> = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter(|line| line.bytes().all(|b| b.is_ascii_alphabetic()))
+            .map(str::to_ascii_lowercase)
+            .collect();
+        words.sort();
+        words.dedup();
+
+        WordList { words }
+    }
+
+    /// Loads the list at `path`. A missing file is an error, not an empty
+    /// list, so a mistyped `--words` is reported as such.
+    pub fn load(path: &Path) -> io::Result<WordList> {
+        fs::read(path).map(|bytes| WordList::parse(&String::from_utf8_lossy(&bytes)))
+    }
+
+    /// Only the words with `len` letters.
+    pub fn with_length(&self, len: usize) -> WordList {
+        WordList {
+            words: self
+                .words
+                .iter()
+                .filter(|word| word.len() == len)
+                .cloned()
+                .collect(),
+        }
+    }
+
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.words
+            .binary_search_by(|probe| probe.as_str().cmp(word))
+            .is_ok()
+    }
+
+    /// Picks a random word, or `None` if the list is empty.
+    pub fn choose<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<&str> {
+        self.words.choose(rng).map(String::as_str)
+    }
+}
+
+/// One round of the word game.
+#[derive(Debug, Clone)]
+pub struct WordGame {
+    list: WordList,
+    secret: String,
+    max_attempts: u32,
+    history: Vec<(String, Vec<Mark>)>,
+}
+
+impl WordGame {
+    /// Starts a game with a secret picked from `list`, whose words must
+    /// all have the same length. Returns `None` if `list` is empty.
+    pub fn new<R: Rng + ?Sized>(list: WordList, rng: &mut R) -> Option<WordGame> {
+        let secret = list.choose(rng)?.to_string();
+
+        Some(WordGame {
+            list,
+            secret,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            history: Vec::new(),
+        })
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> WordGame {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Checks and marks a guess. Invalid guesses do not use up an attempt.
+    ///
+    /// Panics if the game is already over.
+    pub fn guess(&mut self, input: &str) -> Result<&[Mark], WordError> {
+        assert!(!self.is_over(), "Cannot guess after the game is over.");
+
+        let word = input.trim().to_ascii_lowercase();
+        let found = word.chars().count();
+        if found != self.secret.len() {
+            return Err(WordError::WrongLength {
+                expected: self.secret.len(),
+                found,
+            });
+        }
+        if !self.list.contains(&word) {
+            return Err(WordError::NotInList(word));
+        }
+
+        let marks = mark(&self.secret, &word);
+        self.history.push((word, marks));
+        Ok(&self.history.last().unwrap().1)
+    }
+
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+
+    pub fn word_len(&self) -> usize {
+        self.secret.len()
+    }
+
+    pub fn history(&self) -> &[(String, Vec<Mark>)] {
+        &self.history
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.history.len() as u32
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    pub fn is_won(&self) -> bool {
+        matches!(self.history.last(), Some((word, _)) if *word == self.secret)
+    }
+
+    pub fn is_lost(&self) -> bool {
+        !self.is_won() && self.attempts() >= self.max_attempts
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.is_won() || self.is_lost()
+    }
+
+    pub fn score(&self) -> u32 {
+        crate::score::score(self.is_won(), self.attempts(), PAR)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seeded_rng;
+    use Mark::{Absent as A, Correct as C, Present as P};
+
+    #[test]
+    fn marks_letters() {
+        assert_eq!(mark("crane", "crane"), [C, C, C, C, C]);
+        assert_eq!(mark("crane", "nacre"), [P, P, P, P, C]);
+        assert_eq!(mark("crane", "fluff"), [A, A, A, A, A]);
+    }
+
+    #[test]
+    fn repeated_letters_are_only_marked_as_often_as_they_appear() {
+        assert_eq!(mark("crepe", "eerie"), [P, A, P, A, C]);
+        assert_eq!(mark("abbey", "babes"), [P, P, C, C, A]);
+        assert_eq!(mark("robot", "ooooo"), [A, C, A, C, A]);
+    }
+
+    #[test]
+    fn parses_word_lists() {
+        let list = WordList::parse("# five letters\nCrane\n\nslate\ncrane\nno-go\nab\n");
+
+        assert_eq!(list.words(), ["ab", "crane", "slate"]);
+        assert!(list.contains("slate"));
+        assert!(!list.contains("plate"));
+        assert_eq!(list.with_length(5).words(), ["crane", "slate"]);
+    }
+
+    #[test]
+    fn game_checks_guesses() {
+        let list = WordList::parse("crane\nslate\ntrace\n");
+        let mut game = WordGame::new(list, &mut seeded_rng(1))
+            .unwrap()
+            .with_max_attempts(2);
+        let wrong = if game.secret() == "slate" {
+            "crane"
+        } else {
+            "slate"
+        };
+
+        assert_eq!(
+            game.guess("cat"),
+            Err(WordError::WrongLength {
+                expected: 5,
+                found: 3
+            })
+        );
+        assert_eq!(
+            game.guess("plate"),
+            Err(WordError::NotInList("plate".to_string()))
+        );
+        assert!(game.guess(wrong).is_ok());
+        assert!(!game.is_over());
+
+        let secret = game.secret().to_uppercase();
+        assert_eq!(game.guess(&secret), Ok(&[C, C, C, C, C][..]));
+        assert!(game.is_won());
+        assert_eq!(game.score(), 100);
+    }
+
+    #[test]
+    fn builtin_list_is_all_five_letter_words() {
+        let list = WordList::builtin();
+
+        assert!(list.len() > 100);
+        assert_eq!(list.with_length(5), list);
+        assert_eq!(
+            list.len(),
+            BUILTIN
+                .lines()
+                .filter(|line| !line.starts_with('#'))
+                .count()
+        );
+    }
+
+    #[test]
+    fn empty_lists_cannot_start_a_game() {
+        assert!(WordGame::new(WordList::default(), &mut seeded_rng(1)).is_none());
+    }
+}
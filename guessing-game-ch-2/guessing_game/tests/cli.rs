@@ -0,0 +1,639 @@
+mod common;
+
+use guessing_game::bulls_cows::Code;
+use guessing_game::daily;
+use guessing_game::date::Date;
+use guessing_game::words::{WordGame, WordList};
+use guessing_game::{seeded_rng, Game};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn secret_for(seed: u64, range: std::ops::RangeInclusive<i64>) -> i64 {
+    Game::new(range, &mut seeded_rng(seed)).secret()
+}
+
+#[test]
+fn seeded_session_transcript() {
+    let secret = secret_for(7, 1..=100);
+    let below = secret - 1;
+    let above = secret + 1;
+    let input = format!("{}\n{}\n{}\n", below, above, secret);
+
+    let output = common::run(&["--seed", "7"], &input);
+
+    assert!(output.status.success());
+    assert_eq!(
+        common::stdout(&output),
+        format!(
+            "Guess the number! (seed 7)\n\
+             The secret number is between 1 and 100.\n\
+             Please input your guess.\n\
+             You guessed: {below}\n\
+             Too small! (range 1 to 100)\n\
+             Please input your guess.\n\
+             You guessed: {above}\n\
+             Too big! (range 1 to 100)\n\
+             Please input your guess.\n\
+             You guessed: {secret}\n\
+             You win!\n\
+             Guesses: 3 (best possible: 7)\n\
+             Score: 100/100\n\
+             \n\
+             Played 1, won 1 (100%)\n\
+             Guesses to win: mean 3.00, median 3.0\n\
+             Streak: 1 now, 1 best\n       \
+                    3       1  {bar}\n",
+            bar = "#".repeat(40)
+        )
+    );
+}
+
+#[test]
+fn same_seed_replays_identically() {
+    let secret = secret_for(1234, 1..=10);
+    let input = format!("{}\n", secret);
+    let args = ["--seed", "1234", "--difficulty", "easy"];
+
+    let first = common::run(&args, &input);
+    let second = common::run(&args, &input);
+
+    assert!(first.status.success());
+    assert_eq!(first.stdout, second.stdout);
+}
+
+#[test]
+fn bad_arguments_exit_with_usage() {
+    let output = common::run(&["--min", "5", "--max", "1"], "");
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("usage:"));
+}
+
+#[test]
+fn eof_exits_with_distinct_code() {
+    let output = common::run(&["--seed", "7"], "");
+
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No more input"));
+}
+
+#[test]
+fn invalid_input_gets_a_message_and_another_try() {
+    let secret = secret_for(7, 1..=100);
+    let input = format!("\nabc\n-5\n500\n{}\n", secret);
+
+    let output = common::run(&["--seed", "7"], &input);
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("Please type a number."));
+    assert!(stdout.contains("`abc` is not a number."));
+    assert!(stdout.contains("-5 is negative"));
+    assert!(stdout.contains("500 is out of range"));
+    assert!(stdout.contains("You win!\n"));
+}
+
+#[test]
+fn lang_option_translates_the_game() {
+    let secret = secret_for(7, 1..=100);
+    let wrong = if secret == 1 { 2 } else { 1 };
+    let input = format!("abc\n{}\n{}\n", wrong, secret);
+
+    let output = common::run(&["--seed", "7", "--lang", "fr"], &input);
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success());
+    assert!(stdout.starts_with(
+        "Devinez le nombre ! (graine 7)\n\
+         Le nombre secret est compris entre 1 et 100.\n\
+         Veuillez saisir votre proposition.\n"
+    ));
+    assert!(stdout.contains("`abc` n'est pas un nombre."));
+    assert!(stdout.contains("Trop petit ! (intervalle 1 à 100)\n"));
+    assert!(stdout.contains("Vous avez gagné !\nEssais : 2 (meilleur possible : 7)\n"));
+}
+
+#[test]
+fn lang_comes_from_the_environment() {
+    let secret = secret_for(7, 1..=100);
+    let input = format!("{}\n", secret);
+
+    let spanish = common::run_with_env(&[("LANG", "es_ES.UTF-8")], &["--seed", "7"], &input);
+    assert!(common::stdout(&spanish).starts_with("¡Adivina el número! (semilla 7)\n"));
+    assert!(common::stdout(&spanish).contains("¡Has ganado!\n"));
+
+    // `--lang` wins over the environment; unknown locales mean English.
+    let english = common::run_with_env(
+        &[("LANG", "es_ES.UTF-8")],
+        &["--seed", "7", "--lang", "en"],
+        &input,
+    );
+    assert!(common::stdout(&english).starts_with("Guess the number! (seed 7)\n"));
+    let german = common::run_with_env(&[("LANG", "de_DE.UTF-8")], &["--seed", "7"], &input);
+    assert!(common::stdout(&german).contains("You win!\n"));
+
+    let unknown = common::run(&["--lang", "xx"], "");
+    assert_eq!(unknown.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&unknown.stderr).contains("unknown language `xx`"));
+}
+
+#[test]
+fn lang_reaches_the_other_modes() {
+    let reverse = common::run(&["reverse", "--lang", "fr"], "maybe\ncorrect\n");
+    assert_eq!(
+        common::stdout(&reverse),
+        "Pensez à un nombre entre 1 et 100, et je le devinerai.\n\
+         Est-ce 50 ? (higher/lower/correct)\n\
+         `maybe` n'est pas une réponse ; tapez higher, lower ou correct.\n\
+         Est-ce 50 ? (higher/lower/correct)\n\
+         Trouvé ! Votre nombre est 50. Il m'a fallu 1 essais.\n"
+    );
+
+    let cows = common::run(&["cows", "--seed", "1", "--lang", "es"], "12\n");
+    let stdout = common::stdout(&cows);
+    assert!(stdout.starts_with("¡Toros y vacas! (semilla 1)\n"));
+    assert!(stdout.contains("El código tiene 4 cifras, pero escribiste 2."));
+    assert!(String::from_utf8_lossy(&cows.stderr).contains("No hay más entrada; el secreto era"));
+
+    let scores = common::run(&["scores", "--lang", "fr"], "");
+    assert_eq!(common::stdout(&scores), "Pas encore de meilleurs scores.\n");
+
+    let simulate = common::run(
+        &[
+            "simulate",
+            "--games",
+            "2",
+            "--seed",
+            "1",
+            "--strategy",
+            "binary",
+            "--lang",
+            "es",
+        ],
+        "",
+    );
+    let stdout = common::stdout(&simulate);
+    assert!(stdout.starts_with("Simulando 2 partidas de 1 a 100 (semilla 1)\n"));
+    assert!(stdout.contains("binary: media de"));
+
+    let dir = common::data_dir();
+    let forged = dir.join("forged.replay");
+    std::fs::write(
+        &forged,
+        "guessing_game replay v1\nseed 8\nrange 1 100\nguess 10 100 Less\n",
+    )
+    .unwrap();
+    let verify = common::run_in(
+        &dir,
+        &["verify", forged.to_str().unwrap(), "--lang", "fr"],
+        "",
+    );
+    assert_eq!(verify.status.code(), Some(5));
+    assert!(common::stdout(&verify).contains("l'essai n° 1 (100) a été enregistré comme"));
+
+    let missing = dir.join("missing.txt");
+    let words = common::run(
+        &[
+            "words",
+            "--words",
+            missing.to_str().unwrap(),
+            "--lang",
+            "es",
+        ],
+        "",
+    );
+    assert_eq!(words.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&words.stderr).contains("No se encontró"));
+}
+
+#[test]
+fn running_out_of_guesses_reveals_secret() {
+    let secret = secret_for(7, 1..=100);
+    let wrong = if secret == 1 { 2 } else { 1 };
+    let input = format!("{}\n{}\n", wrong, wrong);
+
+    let output = common::run(&["--seed", "7", "--max-guesses", "2"], &input);
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("You have 2 guesses."));
+    assert!(stdout.contains(&format!("You lose! The secret number was {}.", secret)));
+    assert!(stdout.contains("Score: 0/100\n"));
+    assert!(stdout.contains("Played 1, won 0 (0%)\nStreak: 0 now, 0 best\n"));
+}
+
+#[test]
+fn simulate_reports_each_strategy() {
+    let output = common::run(
+        &["simulate", "--games", "50", "--seed", "1", "--max", "64"],
+        "",
+    );
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("binary: mean"));
+    assert!(stdout.contains("random: mean"));
+    assert!(stdout.contains("linear: mean"));
+    assert!(stdout.contains("max 6,") || stdout.contains("max 7,"));
+    assert_eq!(
+        common::stdout(&common::run(
+            &["simulate", "--games", "50", "--seed", "1", "--max", "64"],
+            ""
+        )),
+        stdout
+    );
+}
+
+#[test]
+fn simulate_caps_slow_strategies_on_huge_ranges() {
+    let output = common::run(
+        &[
+            "simulate",
+            "--games",
+            "20",
+            "--seed",
+            "1",
+            "--strategy",
+            "linear",
+            "--min",
+            "0",
+            "--max",
+            "9223372036854775807",
+        ],
+        "",
+    );
+
+    assert!(output.status.success());
+    assert!(common::stdout(&output).contains("linear: mean 64.00 guesses, max 64, won 0/20"));
+}
+
+#[test]
+fn subcommands_refuse_flags_they_would_ignore() {
+    for args in [
+        &["reverse", "--evil"][..],
+        &["reverse", "--json"],
+        &["party", "--players", "a,b", "--record", "x"],
+        &["party", "--players", "a,b", "--evil"],
+        &["daily", "--hints"],
+        &["daily", "--seed", "1"],
+        &["words", "--info"],
+        &["simulate", "--games", "1", "--script", "-"],
+        &["serve", "--addr", "127.0.0.1:0", "--json"],
+    ] {
+        let output = common::run(args, "");
+
+        assert_eq!(output.status.code(), Some(2), "{:?}", args);
+        assert!(
+            String::from_utf8_lossy(&output.stderr).contains("does not take"),
+            "{:?}",
+            args
+        );
+    }
+}
+
+#[test]
+fn reverse_mode_finds_the_number() {
+    let output = common::run(&["reverse", "--max", "10"], "lower\nhigher\nc\n");
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("Is it 5?"));
+    assert!(stdout.contains("Is it 2?"));
+    assert!(stdout.contains("Is it 3?"));
+    assert!(stdout.contains("Got it! Your number is 3. I needed 3 guesses."));
+
+    // With one number left there is nothing to ask.
+    let output = common::run(&["reverse", "--max", "4"], "higher\nhigher\n");
+    let stdout = common::stdout(&output);
+    assert!(output.status.success());
+    assert!(!stdout.contains("Is it 4?"));
+    assert!(stdout.ends_with("Got it! Your number is 4. I needed 2 guesses.\n"));
+}
+
+#[test]
+fn reverse_mode_calls_out_cheating() {
+    let output = common::run(&["reverse", "--max", "10"], "lower\nhigher\nlower\n");
+
+    assert_eq!(output.status.code(), Some(4));
+    assert!(
+        common::stdout(&output).contains("Cheater! You said higher than 2 and then lower than 3.")
+    );
+}
+
+#[test]
+fn bulls_and_cows_checks_input_and_scores_guesses() {
+    let secret = Code::random(3, &mut seeded_rng(4)).to_string();
+    let input = format!("112\n12\n{}\n", secret);
+    let output = common::run(&["cows", "--digits", "3", "--seed", "4"], &input);
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("The digit 1 appears more than once"));
+    assert!(stdout.contains("The code has 3 digits, but you typed 2."));
+    assert!(stdout.contains("3 bulls, 0 cows"));
+    assert!(stdout.contains("You win in 1 guesses!"));
+}
+
+#[test]
+fn bulls_and_cows_solver_wins() {
+    let output = common::run(&["cows", "--seed", "3", "--solve"], "");
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("I guess 0123."));
+    assert!(stdout.contains("Solved in"));
+
+    // Too long to search, so the solver settles for possible codes.
+    let output = common::run(&["cows", "--seed", "3", "--digits", "6", "--solve"], "");
+    assert!(output.status.success());
+    assert!(common::stdout(&output).contains("Solved in"));
+}
+
+#[test]
+fn bulls_and_cows_rejects_numbers_that_do_not_fit() {
+    for args in [["--digits", "4294967300"], ["--max-guesses", "4294967296"]] {
+        let output = common::run(&[&["cows"], &args[..]].concat(), "");
+        assert_eq!(output.status.code(), Some(2), "{:?}", args);
+    }
+}
+
+#[test]
+fn word_game_marks_letters_and_saves_a_score() {
+    let words = "crane\nslate\ntrace\nabbey\nbabes\n";
+    let dir = common::data_dir();
+    std::fs::write(dir.join("words.txt"), words).unwrap();
+    let game = WordGame::new(WordList::parse(words), &mut seeded_rng(6)).unwrap();
+    let wrong = if game.secret() == "babes" {
+        "abbey"
+    } else {
+        "babes"
+    };
+
+    let input = format!("plate\ncat\n{}\n{}\n", wrong, game.secret());
+    let output = common::run_in(&dir, &["words", "--seed", "6", "--name", "wordy"], &input);
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("`plate` is not in the word list."));
+    assert!(stdout.contains("The word has 5 letters, but you typed 3."));
+    assert!(stdout.contains("= = = = ="));
+    assert!(stdout.contains("You win!"));
+
+    let scores = common::stdout(&common::run_in(&dir, &["scores"], ""));
+    assert!(scores.contains("words"));
+    assert!(scores.contains("wordy"));
+}
+
+#[test]
+fn word_game_falls_back_to_the_built_in_list() {
+    let output = common::run(&["words", "--seed", "6"], "");
+    assert_eq!(output.status.code(), Some(3));
+    assert!(common::stdout(&output).contains("The secret word has 5 letters."));
+
+    let output = common::run(&["words", "--length", "7"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("The built-in word list has no 7-letter words."));
+
+    let output = common::run(&["words", "--words", "no-such-list.txt"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no-such-list.txt not found."));
+}
+
+#[test]
+fn hot_seat_party_ranks_the_winner_first() {
+    let secret = secret_for(9, 1..=100);
+    let wrong = if secret == 1 { 2 } else { 1 };
+    let input = format!("{}\n{}\n", wrong, secret);
+
+    let output = common::run(&["party", "--players", "ann,bob", "--seed", "9"], &input);
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("ann, please input your guess."));
+    assert!(stdout.contains("bob got it!"));
+    assert!(stdout.contains(&format!(
+        "  1. bob              won  after 1 guesses: {}",
+        secret
+    )));
+    assert!(stdout.contains(&format!(
+        "  2. ann              lost after 1 guesses: {}",
+        wrong
+    )));
+}
+
+#[test]
+fn party_needs_two_different_players() {
+    assert_eq!(
+        common::run(&["party", "--players", "ann"], "")
+            .status
+            .code(),
+        Some(2)
+    );
+    assert_eq!(
+        common::run(&["party", "--players", "ann,ann"], "")
+            .status
+            .code(),
+        Some(2)
+    );
+}
+
+/// Yesterday, today and tomorrow in UTC, as the game counts days.
+fn days_around_today() -> [Date; 3] {
+    let days = (SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        / 86_400) as i64;
+    [-1, 0, 1].map(|offset| Date::from_unix_days(days + offset))
+}
+
+#[test]
+fn daily_puzzle_shares_arrows_and_allows_one_result() {
+    let dir = common::data_dir();
+    let [_, today, _] = days_around_today();
+    let secret = daily::secret(&(1..=100), today, "team");
+    let (first, arrow) = if secret == 1 { (2, "↓") } else { (1, "↑") };
+    let input = format!("{}\n{}\n", first, secret);
+    let args = ["daily", "--salt", "team", "--name", "ada"];
+
+    let output = common::run_in(&dir, &args, &input);
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success());
+    assert!(stdout.contains(&format!("Daily puzzle for {}!", today)));
+    let share = format!("Daily {}: 2 guesses {}✔", today, arrow);
+    assert!(stdout.ends_with(&format!("\n{}\n", share)));
+
+    let again = common::stdout(&common::run_in(&dir, &args, &input));
+    assert!(again.contains(&format!("ada already played the puzzle for {}.", today)));
+    assert!(again.contains(&share));
+}
+
+#[test]
+fn abandoned_daily_puzzle_counts_as_lost() {
+    let dir = common::data_dir();
+    let [_, today, _] = days_around_today();
+    let secret = daily::secret(&(1..=100), today, "");
+    let (wrong, arrow) = if secret == 1 { (2, "↓") } else { (1, "↑") };
+    let args = ["daily", "--name", "eve"];
+
+    let quit = common::run_in(&dir, &args, &format!("{}\n", wrong));
+    assert_eq!(quit.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&quit.stderr).contains("counts as lost"));
+
+    let again = common::stdout(&common::run_in(&dir, &args, &format!("{}\n", secret)));
+    assert!(again.contains("eve already played the puzzle"));
+    assert!(again.contains(&format!("Daily {}: X {}✘", today, arrow)));
+
+    let stats = common::stdout(&common::run_in(&dir, &["stats"], ""));
+    assert!(stats.starts_with(
+        "Played 1, won 0 (0%)
+"
+    ));
+}
+
+#[test]
+fn only_todays_puzzle_is_recorded() {
+    let dir = common::data_dir();
+    let [yesterday, _, tomorrow] = days_around_today();
+    let secret = daily::secret(&(1..=100), yesterday, "");
+    let yesterday = yesterday.to_string();
+    let args = ["daily", "--date", &yesterday, "--name", "ada"];
+
+    for _ in 0..2 {
+        let output = common::run_in(&dir, &args, &format!("{}\n", secret));
+        let stdout = common::stdout(&output);
+
+        assert!(output.status.success());
+        assert!(stdout.contains(&format!("Practice puzzle for {}!", yesterday)));
+        assert!(stdout.ends_with(&format!("\nPractice {}: 1 guesses ✔\n", yesterday)));
+    }
+    assert!(!dir.join("daily.tsv").exists());
+    assert!(!dir.join("stats.tsv").exists());
+
+    let early = common::run_in(&dir, &["daily", "--date", &tomorrow.to_string()], "");
+    assert_eq!(early.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&early.stderr).contains("is not out yet"));
+}
+
+#[test]
+fn info_reports_bits_per_guess() {
+    let secret = secret_for(7, 1..=100);
+    assert_eq!(secret, 4, "the expected transcript assumes this secret");
+
+    let output = common::run(&["--seed", "7", "--info"], "50\n25\n12\n6\n3\n1\n4\n");
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success());
+    assert!(stdout.contains(
+        "Candidates left: 1 to 49 (49 numbers).\n\
+         That guess earned 1.03 bits; bisection guarantees 1.00.\n"
+    ));
+    assert!(stdout.contains(
+        "  #      guess  result     left    bits   ideal  rating\n\
+         \x20 1         50  Greater       49    1.03    1.00  lucky\n\
+         \x20 2         25  Greater       24    1.03    1.03  ideal\n\
+         \x20 3         12  Greater       11    1.13    1.00  lucky\n\
+         \x20 4          6  Greater        5    1.14    1.14  ideal\n\
+         \x20 5          3  Less           2    1.32    1.32  ideal\n\
+         \x20 6          1  Less           2    0.00    1.00  wasted\n\
+         \x20 7          4  Equal          1    1.00    1.00  ideal\n\
+         Total: 6.64 bits.\n"
+    ));
+}
+
+#[test]
+fn evil_host_verifies_its_transcript() {
+    let output = common::run(&["--evil", "--max", "4"], "2\n3\n4\n");
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("You win!"));
+    assert!(stdout.contains("Guesses: 3"));
+    assert!(stdout.contains("Every answer was consistent with 4."));
+}
+
+#[test]
+fn scripted_json_transcript() {
+    let secret = secret_for(11, 1..=100);
+    let wrong = if secret == 100 { 99 } else { 100 };
+    let dir = common::data_dir();
+    let script = dir.join("guesses.txt");
+    std::fs::write(&script, format!("{}\nnope\n{}\n", wrong, secret)).unwrap();
+
+    let output = common::run_in(
+        &dir,
+        &[
+            "--seed",
+            "11",
+            "--script",
+            script.to_str().unwrap(),
+            "--json",
+        ],
+        "",
+    );
+    let stdout = common::stdout(&output);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    assert!(output.status.success());
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with(&format!(
+        "{{\"guess\":{},\"result\":\"Greater\",\"attempt\":1,\"elapsed_ms\":",
+        wrong
+    )));
+    assert!(lines[1].starts_with(&format!(
+        "{{\"guess\":{},\"result\":\"Equal\",\"attempt\":2,\"elapsed_ms\":",
+        secret
+    )));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("`nope` is not a number."));
+    assert!(!dir.join("scores.tsv").exists());
+}
+
+#[test]
+fn json_output_keeps_hints_out_of_the_records() {
+    let secret = secret_for(11, 1..=100);
+    let input = format!("hint parity\nhint nonsense\n{}\n", secret);
+
+    let output = common::run(
+        &["--seed", "11", "--json", "--hints", "--script", "-"],
+        &input,
+    );
+    let stdout = common::stdout(&output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(output.status.success());
+    assert_eq!(stdout.lines().count(), 1);
+    assert!(stdout.starts_with(&format!("{{\"guess\":{},", secret)));
+    assert!(stderr.contains("(-15 points)"));
+}
+
+#[test]
+fn scripted_stdin_has_no_prompts() {
+    let secret = secret_for(11, 1..=100);
+
+    let output = common::run(&["--seed", "11", "--script", "-"], &format!("{}\n", secret));
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success());
+    assert!(!stdout.contains("Please input your guess."));
+    assert!(stdout.contains("You win!"));
+}
+
+#[test]
+fn hints_report_proximity_and_cost_points() {
+    let secret = secret_for(7, 1..=100);
+    let far = if secret > 50 { 1 } else { 100 };
+    let near = if secret > 50 { secret - 1 } else { secret + 1 };
+    let input = format!("{}\nhint parity\nhint div 0\n{}\n{}\n", far, near, secret);
+
+    let output = common::run(&["--seed", "7", "--hints"], &input);
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("Cold.\n"));
+    assert!(stdout.contains("(-15 points)"));
+    assert!(stdout.contains("is not a whole number of 2 or more."));
+    assert!(stdout.contains("Burning, and closer than last time.\n"));
+    assert!(stdout.contains("Hints cost you 15 points."));
+    assert!(stdout.contains("Score: 85/100"));
+}
@@ -0,0 +1,81 @@
+#![allow(dead_code)]
+
+use std::env;
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A data directory made by [`data_dir`], deleted again when dropped.
+pub struct DataDir(PathBuf);
+
+impl Deref for DataDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for DataDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// A fresh, empty data directory so tests never touch the real one.
+pub fn data_dir() -> DataDir {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let dir = env::temp_dir().join(format!(
+        "guessing_game-test-{}-{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::SeqCst)
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    DataDir(dir)
+}
+
+/// Runs the `guessing_game` binary with `args`, feeding `input` on stdin.
+pub fn run(args: &[&str], input: &str) -> Output {
+    run_in(&data_dir(), args, input)
+}
+
+/// Like [`run`], but keeps saved data in `data_dir`.
+pub fn run_in(data_dir: &Path, args: &[&str], input: &str) -> Output {
+    spawn(data_dir, &[], args, input)
+}
+
+/// Like [`run`], with extra environment variables such as `LANG`.
+pub fn run_with_env(vars: &[(&str, &str)], args: &[&str], input: &str) -> Output {
+    spawn(&data_dir(), vars, args, input)
+}
+
+fn spawn(data_dir: &Path, vars: &[(&str, &str)], args: &[&str], input: &str) -> Output {
+    // The messages checked by tests are English, whatever the host locale.
+    let mut child = Command::new(env!("CARGO_BIN_EXE_guessing_game"))
+        .args(args)
+        .env("GUESSING_GAME_DATA_DIR", data_dir)
+        .env("LANG", "C")
+        .envs(vars.iter().copied())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to start guessing_game");
+
+    // A run that exits before reading its input closes the pipe early.
+    let written = child.stdin.take().unwrap().write_all(input.as_bytes());
+    if let Err(err) = written {
+        assert_eq!(err.kind(), ErrorKind::BrokenPipe, "{}", err);
+    }
+
+    child.wait_with_output().unwrap()
+}
+
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
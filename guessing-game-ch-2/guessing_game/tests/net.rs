@@ -0,0 +1,97 @@
+use guessing_game::interval::Interval;
+use guessing_game::net::{self, Client};
+use guessing_game::protocol::{Command, Reply};
+use guessing_game::strategy::{BinarySearch, GuessStrategy};
+use guessing_game::Config;
+use std::cmp::Ordering;
+use std::io;
+use std::net::{SocketAddr, TcpListener};
+use std::thread;
+
+fn start_server(config: Config) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || net::serve(listener, config, 42));
+    addr
+}
+
+/// Plays one game with binary search and returns the `WIN` attempts.
+fn play(client: &mut Client, min: i64, max: i64) -> u32 {
+    let mut candidates = Interval::new(&(min..=max));
+    loop {
+        let guess = BinarySearch.next_guess(&candidates);
+        match client.send(Command::Guess(guess)).unwrap() {
+            Reply::Less => candidates.narrow(guess, Ordering::Less),
+            Reply::Greater => candidates.narrow(guess, Ordering::Greater),
+            Reply::Win(attempts) => return attempts,
+            other => panic!("unexpected reply {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn clients_play_their_own_games_at_once() {
+    let addr = start_server(Config::default());
+
+    let players: Vec<_> = (0..4)
+        .map(|_| {
+            thread::spawn(move || {
+                let (mut client, greeting) = Client::connect(addr).unwrap();
+                assert_eq!(greeting, Reply::Ready { min: 1, max: 100 });
+
+                let attempts = play(&mut client, 1, 100);
+                assert!((1..=7).contains(&attempts));
+
+                assert_eq!(
+                    client.send(Command::New).unwrap(),
+                    Reply::Ready { min: 1, max: 100 }
+                );
+                play(&mut client, 1, 100);
+
+                assert_eq!(client.send(Command::Quit).unwrap(), Reply::Bye);
+            })
+        })
+        .collect();
+
+    for player in players {
+        player.join().unwrap();
+    }
+}
+
+#[test]
+fn bad_commands_get_errors_not_disconnects() {
+    let addr = start_server(Config {
+        range: -10..=10,
+        max_attempts: Some(1),
+        ..Config::default()
+    });
+    let (mut client, greeting) = Client::connect(addr).unwrap();
+
+    assert_eq!(greeting, Reply::Ready { min: -10, max: 10 });
+    assert!(matches!(client.send_line("HELLO").unwrap(), Reply::Err(_)));
+    assert!(matches!(
+        client.send_line("GUESS 99").unwrap(),
+        Reply::Err(_)
+    ));
+
+    match client.send(Command::Guess(0)).unwrap() {
+        Reply::Win(1) | Reply::Lose(_) => {}
+        other => panic!("unexpected reply {:?}", other),
+    }
+    assert!(matches!(
+        client.send(Command::Guess(0)).unwrap(),
+        Reply::Err(_)
+    ));
+    assert_eq!(client.send(Command::Quit).unwrap(), Reply::Bye);
+}
+
+#[test]
+fn server_gives_up_after_accept_keeps_failing() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    // With no client waiting, every accept fails with `WouldBlock`.
+    listener.set_nonblocking(true).unwrap();
+
+    let err = net::serve(listener, Config::default(), 42).unwrap_err();
+
+    assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+}
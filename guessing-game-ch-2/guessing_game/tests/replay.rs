@@ -0,0 +1,144 @@
+mod common;
+
+use guessing_game::{replay, seeded_rng, Game};
+use std::fs;
+
+#[test]
+fn recorded_games_replay_and_verify() {
+    let dir = common::data_dir();
+    let file = dir.join("game.replay");
+    let file = file.to_str().unwrap();
+    let secret = Game::new(1..=100, &mut seeded_rng(8)).secret();
+    let wrong = if secret == 1 { 2 } else { 1 };
+    let input = format!("{}\n{}\n", wrong, secret);
+
+    let played = common::run_in(&dir, &["--seed", "8", "--record", file], &input);
+    assert!(played.status.success());
+    assert!(fs::read_to_string(file)
+        .unwrap()
+        .starts_with("guessing_game replay v1\nseed 8\nrange 1 100\n"));
+
+    let replayed = common::run_in(&dir, &["replay", file, "--speed", "0"], "");
+    assert!(replayed.status.success());
+    assert_eq!(
+        common::stdout(&replayed),
+        format!(
+            "Replaying seed 8: the secret number is between 1 and 100.\n\
+             You guessed: {wrong}\n\
+             Too small!\n\
+             You guessed: {secret}\n\
+             You win!\n"
+        )
+    );
+
+    let verified = common::run_in(&dir, &["verify", file], "");
+    assert!(verified.status.success());
+    assert!(common::stdout(&verified).contains("all 2 guesses match the engine (seed 8, won)"));
+}
+
+#[test]
+fn replay_speed_must_be_sensible() {
+    let dir = common::data_dir();
+    let file = dir.join("game.replay");
+    let file = file.to_str().unwrap();
+    fs::write(
+        file,
+        "guessing_game replay v1\nseed 1\nrange 1 10\nguess 500 5 Less\n",
+    )
+    .unwrap();
+
+    for speed in ["1e-20", "inf", "NaN", "-1", "fast"] {
+        let output = common::run_in(&dir, &["replay", file, "--speed", speed], "");
+        assert_eq!(output.status.code(), Some(2), "--speed {}", speed);
+    }
+
+    let fast = common::run_in(&dir, &["replay", file, "--speed", "1000000"], "");
+    assert!(fast.status.success());
+}
+
+#[test]
+fn tampered_replays_fail_verification() {
+    let dir = common::data_dir();
+    let file = dir.join("forged.replay");
+    fs::write(
+        &file,
+        "guessing_game replay v1\nseed 8\nrange 1 100\nguess 10 100 Less\n",
+    )
+    .unwrap();
+
+    let output = common::run_in(&dir, &["verify", file.to_str().unwrap()], "");
+
+    assert_eq!(output.status.code(), Some(5));
+    assert!(common::stdout(&output).contains("was recorded as Less but the engine says Greater"));
+}
+
+#[test]
+fn unscripted_games_are_saved_to_the_data_dir() {
+    let dir = common::data_dir();
+    let secret = Game::new(1..=100, &mut seeded_rng(8)).secret();
+
+    let played = common::run_in(&dir, &["--seed", "8"], &format!("{}\n", secret));
+    assert!(played.status.success());
+
+    let saved: Vec<String> = fs::read_dir(dir.join("replays"))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    assert_eq!(saved.len(), 1);
+    assert!(saved[0].ends_with("-seed-8.replay"));
+
+    // The same seed again the same day must not replace the first replay.
+    for _ in 0..2 {
+        let again = common::run_in(&dir, &["--seed", "8"], &format!("{}\n", secret));
+        assert!(again.status.success());
+    }
+    let mut saved: Vec<String> = fs::read_dir(dir.join("replays"))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    saved.sort();
+    assert_eq!(saved.len(), 3);
+    assert!(saved[0].ends_with("-seed-8-2.replay"));
+    assert!(saved[1].ends_with("-seed-8-3.replay"));
+    assert!(saved[2].ends_with("-seed-8.replay"));
+}
+
+#[test]
+fn only_the_newest_saved_replays_are_kept() {
+    let dir = common::data_dir();
+    let replays = dir.join("replays");
+    fs::create_dir_all(&replays).unwrap();
+    for seed in 0..replay::MAX_SAVED {
+        let old = "guessing_game replay v1\nseed 1\nrange 1 10\n";
+        fs::write(
+            replays.join(format!("2000-01-01-seed-{}.replay", seed)),
+            old,
+        )
+        .unwrap();
+    }
+    fs::write(replays.join("notes.txt"), "not a replay").unwrap();
+    let secret = Game::new(1..=100, &mut seeded_rng(8)).secret();
+
+    let played = common::run_in(&dir, &["--seed", "8"], &format!("{}\n", secret));
+    assert!(played.status.success());
+
+    let names: Vec<String> = fs::read_dir(&replays)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    assert_eq!(names.len(), replay::MAX_SAVED + 1);
+    assert!(names.iter().any(|name| name.ends_with("-seed-8.replay")));
+    assert!(names.contains(&"notes.txt".to_string()));
+}
+
+#[test]
+fn newer_replay_versions_are_refused() {
+    let dir = common::data_dir();
+    let file = dir.join("future.replay");
+    fs::write(&file, "guessing_game replay v9\nseed 1\nrange 1 10\n").unwrap();
+
+    let output = common::run_in(&dir, &["verify", file.to_str().unwrap()], "");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("newer than this program"));
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use guessing_game::{seeded_rng, Difficulty, Game};
+use std::fs;
+
+#[test]
+fn finished_games_show_up_in_scores() {
+    let dir = common::data_dir();
+    let secret = Game::new(Difficulty::Easy.range(), &mut seeded_rng(3)).secret();
+    let input = format!("{}\n", secret);
+
+    let played = common::run_in(
+        &dir,
+        &["-d", "easy", "--seed", "3", "--name", "ada"],
+        &input,
+    );
+    assert!(played.status.success());
+
+    let output = common::run_in(&dir, &["scores", "--top", "5"], "");
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success());
+    assert!(stdout.starts_with("easy\n"));
+    assert!(stdout.contains("ada"));
+    assert!(stdout.contains("1 to 10"));
+}
+
+#[test]
+fn corrupted_score_file_does_not_crash() {
+    let dir = common::data_dir();
+    fs::write(
+        dir.join("scores.tsv"),
+        "\u{0}not\ta\tscore\n2026-10-17\tcut",
+    )
+    .unwrap();
+
+    let output = common::run_in(&dir, &["scores"], "");
+    assert!(output.status.success());
+    assert_eq!(common::stdout(&output), "No high scores yet.\n");
+
+    let played = common::run_in(&dir, &["--seed", "1", "--max-guesses", "1"], "50\n");
+    assert!(played.status.success());
+    assert!(String::from_utf8_lossy(&played.stderr).is_empty());
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use guessing_game::stats::Stats;
+use guessing_game::{seeded_rng, Game};
+use std::fs;
+
+#[test]
+fn stats_build_up_across_games() {
+    let dir = common::data_dir();
+    let secret = Game::new(1..=100, &mut seeded_rng(5)).secret();
+
+    let won = common::run_in(&dir, &["--seed", "5"], &format!("{}\n", secret));
+    assert!(won.status.success());
+    let wrong = if secret == 1 { 2 } else { 1 };
+    let lost = common::run_in(
+        &dir,
+        &["--seed", "5", "--max-guesses", "1"],
+        &format!("{}\n", wrong),
+    );
+    assert!(common::stdout(&lost).contains("Played 2, won 1 (50%)"));
+
+    let output = common::run_in(&dir, &["stats"], "");
+    let stdout = common::stdout(&output);
+
+    assert!(output.status.success());
+    assert!(stdout.starts_with("Played 2, won 1 (50%)\n"));
+    assert!(stdout.contains("Guesses to win: mean 1.00, median 1.0"));
+    assert!(stdout.contains("Streak: 0 now, 1 best"));
+}
+
+#[test]
+fn stats_merge_another_machine() {
+    let dir = common::data_dir();
+    let mut other = Stats::default();
+    other.record(true, 4);
+    other.record(true, 6);
+    let file = dir.join("other.tsv");
+    fs::write(&file, other.to_text()).unwrap();
+
+    let output = common::run_in(&dir, &["stats", "--merge", file.to_str().unwrap()], "");
+    assert!(output.status.success());
+    assert!(common::stdout(&output).contains("Streak: 2 now, 2 best"));
+
+    let saved = Stats::parse(&fs::read_to_string(dir.join("stats.tsv")).unwrap());
+    assert_eq!(saved, other);
+}
+
+#[test]
+fn no_stats_yet() {
+    let output = common::run(&["stats"], "");
+
+    assert!(output.status.success());
+    assert_eq!(common::stdout(&output), "No games played yet.\n");
+}
+
+#[test]
+fn huge_counts_in_the_stats_file_do_not_overflow() {
+    let dir = common::data_dir();
+    let max = u32::MAX;
+    fs::write(
+        dir.join("stats.tsv"),
+        format!("games\t{max}\nwins\t{max}\ncurrent_streak\t{max}\nwon_in\t1\t{max}\n"),
+    )
+    .unwrap();
+    let secret = Game::new(1..=100, &mut seeded_rng(5)).secret();
+
+    let won = common::run_in(&dir, &["--seed", "5"], &format!("{}\n", secret));
+    assert!(won.status.success());
+    assert!(common::stdout(&won).contains(&format!("Played {max}, won {max} (100%)")));
+
+    let merged = common::run_in(
+        &dir,
+        &["stats", "--merge", dir.join("stats.tsv").to_str().unwrap()],
+        "",
+    );
+    assert!(merged.status.success());
+    assert!(common::stdout(&merged).contains(&format!("Streak: {max} now, {max} best")));
+}
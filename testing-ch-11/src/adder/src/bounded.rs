@@ -0,0 +1,361 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::num::IntErrorKind;
+use std::str::FromStr;
+
+/// An integer that is always within `MIN..=MAX`.
+///
+/// Every way of making one checks the bounds, so code that is handed a
+/// `Bounded` never has to check them again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bounded<const MIN: i64, const MAX: i64> {
+    value: i64,
+}
+
+/// Why a value cannot be a [`Bounded`]: it broke one of the bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundedError {
+    TooSmall { value: i64, min: i64 },
+    TooLarge { value: i64, max: i64 },
+}
+
+impl fmt::Display for BoundedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BoundedError::TooSmall { value, min } => write!(
+                f,
+                "Value must be greater than or equal to {}, got {}.",
+                min, value
+            ),
+            BoundedError::TooLarge { value, max } => write!(
+                f,
+                "Value must be less than or equal to {}, got {}.",
+                max, value
+            ),
+        }
+    }
+}
+
+impl Error for BoundedError {}
+
+impl<const MIN: i64, const MAX: i64> Bounded<MIN, MAX> {
+    pub const MIN: i64 = MIN;
+    pub const MAX: i64 = MAX;
+
+    /// Fails to compile for a type like `Bounded<10, 1>` with no values.
+    const NOT_EMPTY: () = assert!(MIN <= MAX, "Bounded needs MIN <= MAX");
+
+    pub fn try_new(value: i64) -> Result<Self, BoundedError> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::NOT_EMPTY;
+
+        if value < MIN {
+            Err(BoundedError::TooSmall { value, min: MIN })
+        } else if value > MAX {
+            Err(BoundedError::TooLarge { value, max: MAX })
+        } else {
+            Ok(Bounded { value })
+        }
+    }
+
+    pub fn new(value: i64) -> Self {
+        match Self::try_new(value) {
+            Ok(bounded) => bounded,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    pub fn value(self) -> i64 {
+        self.value
+    }
+
+    /// Every valid value, from `MIN` to `MAX`.
+    pub fn all() -> impl DoubleEndedIterator<Item = Self> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::NOT_EMPTY;
+
+        (MIN..=MAX).map(|value| Bounded { value })
+    }
+
+    /// `self + rhs`, or `None` if that is out of bounds.
+    pub fn checked_add(self, rhs: i64) -> Option<Self> {
+        self.value
+            .checked_add(rhs)
+            .and_then(|sum| Self::try_new(sum).ok())
+    }
+
+    /// `self - rhs`, or `None` if that is out of bounds.
+    pub fn checked_sub(self, rhs: i64) -> Option<Self> {
+        self.value
+            .checked_sub(rhs)
+            .and_then(|diff| Self::try_new(diff).ok())
+    }
+
+    /// `self + rhs`, stopping at the nearest bound.
+    pub fn saturating_add(self, rhs: i64) -> Self {
+        Self::clamped(i128::from(self.value) + i128::from(rhs))
+    }
+
+    /// `self - rhs`, stopping at the nearest bound.
+    pub fn saturating_sub(self, rhs: i64) -> Self {
+        Self::clamped(i128::from(self.value) - i128::from(rhs))
+    }
+
+    /// `self + rhs`, going round to `MIN` after `MAX` and back.
+    pub fn wrapping_add(self, rhs: i64) -> Self {
+        Self::wrapped(i128::from(self.value) + i128::from(rhs))
+    }
+
+    /// `self - rhs`, going round to `MAX` before `MIN` and back.
+    pub fn wrapping_sub(self, rhs: i64) -> Self {
+        Self::wrapped(i128::from(self.value) - i128::from(rhs))
+    }
+
+    fn clamped(value: i128) -> Self {
+        let value = value.clamp(i128::from(MIN), i128::from(MAX));
+        Bounded {
+            value: value as i64,
+        }
+    }
+
+    fn wrapped(value: i128) -> Self {
+        // At most 2^64 values, so all of this fits in an i128.
+        let span = i128::from(MAX) - i128::from(MIN) + 1;
+        let offset = (value - i128::from(MIN)).rem_euclid(span);
+        Bounded {
+            value: (i128::from(MIN) + offset) as i64,
+        }
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> TryFrom<i64> for Bounded<MIN, MAX> {
+    type Error = BoundedError;
+
+    fn try_from(value: i64) -> Result<Self, BoundedError> {
+        Self::try_new(value)
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> fmt::Display for Bounded<MIN, MAX> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> PartialEq<i64> for Bounded<MIN, MAX> {
+    fn eq(&self, other: &i64) -> bool {
+        self.value == *other
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> PartialEq<Bounded<MIN, MAX>> for i64 {
+    fn eq(&self, other: &Bounded<MIN, MAX>) -> bool {
+        *self == other.value
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> PartialOrd<i64> for Bounded<MIN, MAX> {
+    fn partial_cmp(&self, other: &i64) -> Option<Ordering> {
+        self.value.partial_cmp(other)
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> PartialOrd<Bounded<MIN, MAX>> for i64 {
+    fn partial_cmp(&self, other: &Bounded<MIN, MAX>) -> Option<Ordering> {
+        self.partial_cmp(&other.value)
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> PartialEq<i32> for Bounded<MIN, MAX> {
+    fn eq(&self, other: &i32) -> bool {
+        self.value == i64::from(*other)
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> PartialEq<Bounded<MIN, MAX>> for i32 {
+    fn eq(&self, other: &Bounded<MIN, MAX>) -> bool {
+        i64::from(*self) == other.value
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> PartialOrd<i32> for Bounded<MIN, MAX> {
+    fn partial_cmp(&self, other: &i32) -> Option<Ordering> {
+        self.value.partial_cmp(&i64::from(*other))
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> PartialOrd<Bounded<MIN, MAX>> for i32 {
+    fn partial_cmp(&self, other: &Bounded<MIN, MAX>) -> Option<Ordering> {
+        i64::from(*self).partial_cmp(&other.value)
+    }
+}
+
+/// Why a string is not a [`Bounded`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseBoundedError {
+    /// Nothing but whitespace.
+    Empty,
+    /// Not a whole number; holds the trimmed input.
+    NotANumber(String),
+    /// A whole number, but not from `min` to `max`. It may not even fit in
+    /// an `i64`, so this holds the trimmed input rather than the value.
+    OutOfRange { input: String, min: i64, max: i64 },
+}
+
+impl fmt::Display for ParseBoundedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseBoundedError::Empty => write!(f, "Please type a number."),
+            ParseBoundedError::NotANumber(input) => write!(f, "`{}` is not a number.", input),
+            ParseBoundedError::OutOfRange { input, min, max } => {
+                write!(f, "{} is not between {} and {}.", input, min, max)
+            }
+        }
+    }
+}
+
+impl Error for ParseBoundedError {}
+
+impl<const MIN: i64, const MAX: i64> FromStr for Bounded<MIN, MAX> {
+    type Err = ParseBoundedError;
+
+    fn from_str(s: &str) -> Result<Self, ParseBoundedError> {
+        let input = s.trim();
+        let out_of_range = || ParseBoundedError::OutOfRange {
+            input: input.to_string(),
+            min: MIN,
+            max: MAX,
+        };
+
+        match input.parse() {
+            Ok(value) => Self::try_new(value).map_err(|_| out_of_range()),
+            Err(err) => match err.kind() {
+                IntErrorKind::Empty => Err(ParseBoundedError::Empty),
+                IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => Err(out_of_range()),
+                _ => Err(ParseBoundedError::NotANumber(input.to_string())),
+            },
+        }
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> TryFrom<&str> for Bounded<MIN, MAX> {
+    type Error = ParseBoundedError;
+
+    fn try_from(s: &str) -> Result<Self, ParseBoundedError> {
+        s.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Dice = Bounded<1, 6>;
+    type Full = Bounded<{ i64::MIN }, { i64::MAX }>;
+
+    #[test]
+    fn checks_both_bounds() {
+        assert_eq!(
+            Dice::try_new(0),
+            Err(BoundedError::TooSmall { value: 0, min: 1 })
+        );
+        assert_eq!(
+            Dice::try_from(7),
+            Err(BoundedError::TooLarge { value: 7, max: 6 })
+        );
+        assert!(Dice::try_new(1).is_ok());
+        assert!(Dice::try_new(6).is_ok());
+    }
+
+    #[test]
+    fn checked_arithmetic_stays_in_bounds() {
+        assert_eq!(Dice::new(4).checked_add(2), Some(Dice::new(6)));
+        assert_eq!(Dice::new(4).checked_add(3), None);
+        assert_eq!(Dice::new(2).checked_sub(1), Some(Dice::new(1)));
+        assert_eq!(Dice::new(2).checked_sub(i64::MAX), None);
+        assert_eq!(Full::new(i64::MAX).checked_add(1), None);
+    }
+
+    #[test]
+    fn saturating_arithmetic_stops_at_the_bounds() {
+        assert_eq!(Dice::new(4).saturating_add(100), Dice::new(6));
+        assert_eq!(Dice::new(4).saturating_sub(100), Dice::new(1));
+        assert_eq!(Dice::new(4).saturating_add(-1), Dice::new(3));
+        assert_eq!(Full::new(0).saturating_sub(i64::MIN), Full::new(i64::MAX));
+    }
+
+    #[test]
+    fn wrapping_arithmetic_goes_round() {
+        assert_eq!(Dice::new(6).wrapping_add(1), Dice::new(1));
+        assert_eq!(Dice::new(1).wrapping_sub(1), Dice::new(6));
+        assert_eq!(Dice::new(3).wrapping_add(6 * 1000 + 2), Dice::new(5));
+        assert_eq!(Dice::new(3).wrapping_sub(i64::MIN), Dice::new(5));
+        assert_eq!(Full::new(i64::MAX).wrapping_add(1), Full::new(i64::MIN));
+    }
+
+    #[test]
+    fn iterates_over_every_value_in_order() {
+        let all: Vec<String> = Dice::all().map(|dice| dice.to_string()).collect();
+
+        assert_eq!(all, ["1", "2", "3", "4", "5", "6"]);
+        assert_eq!(Dice::all().next_back(), Some(Dice::new(6)));
+        assert!(Dice::all().zip(Dice::all().skip(1)).all(|(a, b)| a < b));
+        assert_eq!(Bounded::<-2, -2>::all().count(), 1);
+    }
+
+    #[test]
+    fn parses_and_prints() {
+        assert_eq!(" 5 ".parse::<Dice>(), Ok(Dice::new(5)));
+        assert_eq!(Dice::try_from("6"), Ok(Dice::new(6)));
+        assert_eq!(Bounded::<-10, 10>::new(-3).to_string(), "-3");
+        assert_eq!(format!("{:>3}", Dice::new(4)), "  4");
+    }
+
+    #[test]
+    fn parse_errors_say_what_was_wrong() {
+        let out_of_range = |input: &str| ParseBoundedError::OutOfRange {
+            input: input.to_string(),
+            min: 1,
+            max: 6,
+        };
+
+        assert_eq!("  ".parse::<Dice>(), Err(ParseBoundedError::Empty));
+        assert_eq!(
+            Dice::try_from(" five\n"),
+            Err(ParseBoundedError::NotANumber("five".to_string()))
+        );
+        assert_eq!(
+            "4.5".parse::<Dice>(),
+            Err(ParseBoundedError::NotANumber("4.5".to_string()))
+        );
+        assert_eq!("9".parse::<Dice>(), Err(out_of_range("9")));
+        assert_eq!("-0".parse::<Dice>(), Err(out_of_range("-0")));
+        assert_eq!(
+            "99999999999999999999".parse::<Dice>(),
+            Err(out_of_range("99999999999999999999"))
+        );
+        assert_eq!(out_of_range("9").to_string(), "9 is not between 1 and 6.");
+        assert_eq!(
+            ParseBoundedError::NotANumber("x".to_string()).to_string(),
+            "`x` is not a number."
+        );
+    }
+
+    #[test]
+    fn compares_with_plain_integers() {
+        let dice = Dice::new(4);
+
+        assert_eq!(dice.value(), 4);
+        assert_eq!(dice, 4);
+        assert_eq!(4, dice);
+        assert!(dice < 5 && dice > 3 && dice <= 4);
+        assert!(5 > dice && 3 < dice && 4 >= dice);
+        assert!(dice != 6);
+        assert_eq!(dice, 4i32);
+        assert_eq!(4i32, dice);
+        assert_eq!(dice, 4i64);
+        assert!(dice < 5i32 && dice > -1i32);
+        assert!(3i32 < dice && i64::MIN < dice);
+    }
+}
@@ -0,0 +1,248 @@
+use crate::bounded::{Bounded, BoundedError, ParseBoundedError};
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A guess in the guessing game: a number from 1 to 100.
+///
+/// A [`Bounded`] underneath, with `i32` values and errors that say they
+/// are about a guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Guess {
+    value: Bounded<1, 100>,
+}
+
+/// Why a value cannot be a [`Guess`]: it broke one of the bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuessError {
+    TooSmall { value: i32, min: i32 },
+    TooLarge { value: i32, max: i32 },
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GuessError::TooSmall { value, min } => write!(
+                f,
+                "Guess value must be greater than or equal to {}, got {}.",
+                min, value
+            ),
+            GuessError::TooLarge { value, max } => write!(
+                f,
+                "Guess value must be less than or equal to {}, got {}.",
+                max, value
+            ),
+        }
+    }
+}
+
+impl Error for GuessError {}
+
+impl Guess {
+    pub const MIN: i32 = 1;
+    pub const MAX: i32 = 100;
+
+    pub fn try_new(value: i32) -> Result<Guess, GuessError> {
+        match Bounded::try_new(i64::from(value)) {
+            Ok(value) => Ok(Guess { value }),
+            Err(BoundedError::TooSmall { .. }) => Err(GuessError::TooSmall {
+                value,
+                min: Guess::MIN,
+            }),
+            Err(BoundedError::TooLarge { .. }) => Err(GuessError::TooLarge {
+                value,
+                max: Guess::MAX,
+            }),
+        }
+    }
+
+    pub fn new(value: i32) -> Guess {
+        match Guess::try_new(value) {
+            Ok(guess) => guess,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    pub fn value(self) -> i32 {
+        // Always from 1 to 100, so this cannot truncate.
+        self.value.value() as i32
+    }
+
+    /// Every guess, from 1 to 100.
+    pub fn all() -> impl DoubleEndedIterator<Item = Guess> {
+        Bounded::all().map(|value| Guess { value })
+    }
+
+    /// `self + rhs`, or `None` if that is out of bounds.
+    pub fn checked_add(self, rhs: i32) -> Option<Guess> {
+        self.value
+            .checked_add(i64::from(rhs))
+            .map(|value| Guess { value })
+    }
+
+    /// `self - rhs`, or `None` if that is out of bounds.
+    pub fn checked_sub(self, rhs: i32) -> Option<Guess> {
+        self.value
+            .checked_sub(i64::from(rhs))
+            .map(|value| Guess { value })
+    }
+
+    /// `self + rhs`, stopping at 1 or 100.
+    pub fn saturating_add(self, rhs: i32) -> Guess {
+        Guess {
+            value: self.value.saturating_add(i64::from(rhs)),
+        }
+    }
+
+    /// `self - rhs`, stopping at 1 or 100.
+    pub fn saturating_sub(self, rhs: i32) -> Guess {
+        Guess {
+            value: self.value.saturating_sub(i64::from(rhs)),
+        }
+    }
+
+    /// `self + rhs`, going round to 1 after 100 and back.
+    pub fn wrapping_add(self, rhs: i32) -> Guess {
+        Guess {
+            value: self.value.wrapping_add(i64::from(rhs)),
+        }
+    }
+
+    /// `self - rhs`, going round to 100 before 1 and back.
+    pub fn wrapping_sub(self, rhs: i32) -> Guess {
+        Guess {
+            value: self.value.wrapping_sub(i64::from(rhs)),
+        }
+    }
+}
+
+impl From<Guess> for Bounded<1, 100> {
+    fn from(guess: Guess) -> Bounded<1, 100> {
+        guess.value
+    }
+}
+
+impl From<Bounded<1, 100>> for Guess {
+    fn from(value: Bounded<1, 100>) -> Guess {
+        Guess { value }
+    }
+}
+
+impl TryFrom<i32> for Guess {
+    type Error = GuessError;
+
+    fn try_from(value: i32) -> Result<Guess, GuessError> {
+        Guess::try_new(value)
+    }
+}
+
+impl fmt::Display for Guess {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl FromStr for Guess {
+    type Err = ParseBoundedError;
+
+    fn from_str(s: &str) -> Result<Guess, ParseBoundedError> {
+        s.parse().map(|value| Guess { value })
+    }
+}
+
+impl TryFrom<&str> for Guess {
+    type Error = ParseBoundedError;
+
+    fn try_from(s: &str) -> Result<Guess, ParseBoundedError> {
+        s.parse()
+    }
+}
+
+impl PartialEq<i64> for Guess {
+    fn eq(&self, other: &i64) -> bool {
+        self.value == *other
+    }
+}
+
+impl PartialEq<Guess> for i64 {
+    fn eq(&self, other: &Guess) -> bool {
+        *self == other.value
+    }
+}
+
+impl PartialOrd<i64> for Guess {
+    fn partial_cmp(&self, other: &i64) -> Option<Ordering> {
+        self.value.partial_cmp(other)
+    }
+}
+
+impl PartialOrd<Guess> for i64 {
+    fn partial_cmp(&self, other: &Guess) -> Option<Ordering> {
+        self.partial_cmp(&other.value)
+    }
+}
+
+impl PartialEq<i32> for Guess {
+    fn eq(&self, other: &i32) -> bool {
+        self.value == *other
+    }
+}
+
+impl PartialEq<Guess> for i32 {
+    fn eq(&self, other: &Guess) -> bool {
+        *self == other.value
+    }
+}
+
+impl PartialOrd<i32> for Guess {
+    fn partial_cmp(&self, other: &i32) -> Option<Ordering> {
+        self.value.partial_cmp(other)
+    }
+}
+
+impl PartialOrd<Guess> for i32 {
+    fn partial_cmp(&self, other: &Guess) -> Option<Ordering> {
+        self.partial_cmp(&other.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_keeps_guesses_in_bounds() {
+        assert_eq!(Guess::new(99).checked_add(1), Some(Guess::new(100)));
+        assert_eq!(Guess::new(99).checked_add(2), None);
+        assert_eq!(Guess::new(5).saturating_sub(i32::MAX), Guess::new(1));
+        assert_eq!(Guess::new(100).wrapping_add(1), Guess::new(1));
+    }
+
+    #[test]
+    fn compares_with_i32_and_i64() {
+        let guess = Guess::new(5);
+
+        assert!(guess == 5i32);
+        assert!(guess < 50i32);
+        assert!(5i32 == guess && 50i32 > guess);
+        assert!(guess == 5i64 && guess < 50i64);
+        assert!(guess == 5 && guess != 6);
+        assert!(guess > i32::MIN && guess < i64::MAX);
+    }
+
+    #[test]
+    fn parses_prints_and_converts() {
+        assert_eq!(" 42 ".parse::<Guess>(), Ok(Guess::new(42)));
+        assert_eq!(
+            Guess::try_from("7").map(|guess| guess.to_string()),
+            Ok("7".to_string())
+        );
+        assert_eq!(
+            Guess::try_from(0),
+            Err(GuessError::TooSmall { value: 0, min: 1 })
+        );
+        assert_eq!(Bounded::from(Guess::new(3)), Bounded::<1, 100>::new(3));
+        assert_eq!(Guess::all().next_back(), Some(Guess::new(Guess::MAX)));
+    }
+}
@@ -1,105 +1,166 @@
-// #[derive(Debug)]
-// struct Rectangle {
-//     width: u32,
-//     height: u32,
-// }
-
-// impl Rectangle {
-//     fn can_hold(&self, other: &Rectangle) -> bool {
-//         self.width > other.width && self.height > other.height
-//     }
-// }
-
-// #[cfg(test)]
-// mod tests {
-//     use super::*;
-
-//     #[test]
-//     fn larger_can_hold_smaller() {
-//         let larger = Rectangle {
-//             width: 8,
-//             height: 7,
-//         };
-//         let smaller = Rectangle {
-//             width: 5,
-//             height: 1,
-//         };
-
-//         assert!(larger.can_hold(&smaller));
-//     }
-
-//     #[test]
-//     fn smaller_cannot_hold_larger() {
-//         let larger = Rectangle {
-//             width: 8,
-//             height: 7,
-//         };
-//         let smaller = Rectangle {
-//             width: 5,
-//             height: 1,
-//         };
-
-//         assert!(!smaller.can_hold(&larger));
-//     }
-// }
-
-// pub fn add_two(a: i32) -> i32 {
-//     a + 3
-// }
-
-// #[cfg(test)]
-// mod tests {
-//     use super::*;
-
-//     #[test]
-//     fn it_adds_two() {
-//         assert_eq!(4, add_two(2));
-//     }
-// }
-
-// pub fn greeting(name: &str) -> String {
-//     String::from("Hello!")
-// }
-
-// #[test]
-// fn greeting_contains_name() {
-//     let result = greeting("Carol");
-//     assert!(
-//         result.contains("Carol"),
-//         "Greeting did not contain name, value was `{}`",
-//         result
-//     );
-// }
-
-pub struct Guess { 
-    value: i32
+mod bounded;
+mod guess;
+pub mod prop;
+
+pub use bounded::{Bounded, BoundedError, ParseBoundedError};
+pub use guess::{Guess, GuessError};
+
+#[derive(Debug)]
+pub struct Rectangle {
+    pub width: u32,
+    pub height: u32,
 }
 
-impl Guess {
-    pub fn new(value: i32) -> Guess {
-        if value < 1 {
-            panic!(
-                "Guess value must be less than or equal to 100, got {}.",
-                value
-            );
-        } else if value > 100 {
-            panic!(
-                "Guess value must be greater than or equal to 1, got {}.",
-                value
-            );
-        }
-
-        Guess { value }
+impl Rectangle {
+    /// Whether `other` fits inside `self` with room to spare on both sides.
+    ///
+    /// ```
+    /// use adder::Rectangle;
+    ///
+    /// let larger = Rectangle { width: 8, height: 7 };
+    /// let smaller = Rectangle { width: 5, height: 1 };
+    ///
+    /// assert!(larger.can_hold(&smaller));
+    /// assert!(!smaller.can_hold(&larger));
+    /// ```
+    pub fn can_hold(&self, other: &Rectangle) -> bool {
+        self.width > other.width && self.height > other.height
     }
 }
 
+/// Adds two to `a`.
+///
+/// ```
+/// assert_eq!(adder::add_two(2), 4);
+/// ```
+pub fn add_two(a: i32) -> i32 {
+    a + 2
+}
+
+/// Returns a greeting for `name`, such as `Hello Carol!`.
+///
+/// ```
+/// assert_eq!(adder::greeting("Carol"), "Hello Carol!");
+/// ```
+pub fn greeting(name: &str) -> String {
+    format!("Hello {}!", name)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn larger_can_hold_smaller() {
+        let larger = Rectangle {
+            width: 8,
+            height: 7,
+        };
+        let smaller = Rectangle {
+            width: 5,
+            height: 1,
+        };
+
+        assert!(larger.can_hold(&smaller));
+    }
+
+    #[test]
+    fn smaller_cannot_hold_larger() {
+        let larger = Rectangle {
+            width: 8,
+            height: 7,
+        };
+        let smaller = Rectangle {
+            width: 5,
+            height: 1,
+        };
+
+        assert!(!smaller.can_hold(&larger));
+    }
+
+    #[test]
+    fn equal_side_does_not_fit() {
+        let square = Rectangle {
+            width: 5,
+            height: 5,
+        };
+        let wide = Rectangle {
+            width: 5,
+            height: 1,
+        };
+
+        assert!(!square.can_hold(&wide));
+    }
+
+    #[test]
+    fn it_adds_two() {
+        assert_eq!(4, add_two(2));
+        assert_eq!(0, add_two(-2));
+    }
+
+    #[test]
+    fn greeting_contains_name() {
+        let result = greeting("Carol");
+        assert!(
+            result.contains("Carol"),
+            "Greeting did not contain name, value was `{}`",
+            result
+        );
+    }
+
     #[test]
     #[should_panic(expected = "Guess value must be less than or equal to 100")]
     fn greater_than_100() {
         Guess::new(200);
     }
-}
\ No newline at end of file
+
+    #[test]
+    #[should_panic(expected = "Guess value must be greater than or equal to 1")]
+    fn less_than_1() {
+        Guess::new(0);
+    }
+
+    #[test]
+    fn try_new_reports_the_broken_bound() {
+        assert_eq!(
+            Guess::try_new(0).err(),
+            Some(GuessError::TooSmall { value: 0, min: 1 })
+        );
+        assert_eq!(
+            Guess::try_new(101).err(),
+            Some(GuessError::TooLarge {
+                value: 101,
+                max: 100
+            })
+        );
+        assert_eq!(
+            GuessError::TooLarge {
+                value: 200,
+                max: 100
+            }
+            .to_string(),
+            "Guess value must be less than or equal to 100, got 200."
+        );
+    }
+
+    #[test]
+    fn only_1_to_100_are_guesses() {
+        let is_guess = |&n: &i64| {
+            let n = i32::try_from(n).unwrap();
+            Guess::try_new(n).is_ok() == (1..=100).contains(&n)
+        };
+
+        prop::check(prop::ints(-1000..=1000), is_guess);
+        prop::check(
+            prop::ints(i64::from(i32::MIN)..=i64::from(i32::MAX)),
+            is_guess,
+        );
+    }
+
+    #[test]
+    fn try_new_accepts_both_bounds() {
+        assert_eq!(Guess::try_new(1).map(Guess::value), Ok(1));
+        assert_eq!(Guess::try_new(100).map(Guess::value), Ok(100));
+        assert_eq!(Guess::all().count(), 100);
+    }
+}
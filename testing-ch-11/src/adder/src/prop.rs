@@ -0,0 +1,381 @@
+//! Property-based testing with nothing but std.
+//!
+//! A property is a function that should return `true` for every value a
+//! [`Generator`] can make. [`check`] tries it on many random values and,
+//! if one fails, shrinks that value to a minimal counterexample before
+//! panicking with it:
+//!
+//! ```
+//! use adder::prop::{self, ints};
+//!
+//! prop::check(ints(-1000..=1000), |&n| n * 2 / 2 == n);
+//! ```
+//!
+//! Runs are seeded, so a failure comes back every time until it is fixed.
+
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+
+/// A small, fast, seeded random number generator (SplitMix64).
+///
+/// Not for cryptography; it only has to be reproducible.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, every one equally likely.
+    pub fn in_range(&mut self, range: &RangeInclusive<i64>) -> i64 {
+        let (low, high) = (i128::from(*range.start()), i128::from(*range.end()));
+        assert!(low <= high, "empty range {:?}", range);
+
+        let span = (high - low + 1) as u128;
+        // Two draws give 128 bits, so the modulo bias is negligible.
+        let bits = (u128::from(self.next_u64()) << 64) | u128::from(self.next_u64());
+        (low + (bits % span) as i128) as i64
+    }
+}
+
+/// Makes random values, and simpler variations of a value for shrinking.
+pub trait Generator {
+    type Value: Clone + Debug;
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value;
+
+    /// Values simpler than `value`, most promising first. Shrinking keeps
+    /// the first one that still fails, so this must never lead back to
+    /// `value` or the search would not end.
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value>;
+}
+
+/// Integers in `range`, shrinking towards zero, or towards the bound
+/// nearest zero if zero is not in `range`.
+pub fn ints(range: RangeInclusive<i64>) -> Ints {
+    assert!(!range.is_empty(), "empty range {:?}", range);
+    Ints { range }
+}
+
+#[derive(Debug, Clone)]
+pub struct Ints {
+    range: RangeInclusive<i64>,
+}
+
+impl Generator for Ints {
+    type Value = i64;
+
+    fn generate(&self, rng: &mut Rng) -> i64 {
+        // Edge cases find most bugs, so they come up far more often than
+        // their share of a big range.
+        let (low, high) = (*self.range.start(), *self.range.end());
+        match rng.next_u64() % 8 {
+            0 => low,
+            1 => high,
+            2 => self.target(),
+            _ => rng.in_range(&self.range),
+        }
+    }
+
+    fn shrink(&self, &value: &i64) -> Vec<i64> {
+        let target = i128::from(self.target());
+        let mut distance = i128::from(value) - target;
+        let mut candidates = Vec::new();
+
+        // The target itself, then ever closer to `value`: a binary search
+        // for the boundary between passing and failing.
+        while distance != 0 {
+            candidates.push((i128::from(value) - distance) as i64);
+            distance /= 2;
+        }
+        candidates
+    }
+}
+
+impl Ints {
+    fn target(&self) -> i64 {
+        0.clamp(*self.range.start(), *self.range.end())
+    }
+}
+
+/// Printable ASCII strings of up to `max_len` characters, shrinking
+/// towards fewer characters and towards `a`.
+pub fn strings(max_len: usize) -> Strings {
+    Strings { max_len }
+}
+
+#[derive(Debug, Clone)]
+pub struct Strings {
+    max_len: usize,
+}
+
+impl Generator for Strings {
+    type Value = String;
+
+    fn generate(&self, rng: &mut Rng) -> String {
+        let len = rng.in_range(&(0..=self.max_len as i64));
+        (0..len)
+            .map(|_| rng.in_range(&(0x20..=0x7e)) as u8 as char)
+            .collect()
+    }
+
+    fn shrink(&self, value: &String) -> Vec<String> {
+        let chars: Vec<char> = value.chars().collect();
+        let mut candidates: Vec<String> = shorter(&chars)
+            .into_iter()
+            .map(|chars| chars.into_iter().collect())
+            .collect();
+
+        for (i, &c) in chars.iter().enumerate() {
+            if c != 'a' {
+                let mut simpler = chars.clone();
+                simpler[i] = 'a';
+                candidates.push(simpler.into_iter().collect());
+            }
+        }
+        candidates
+    }
+}
+
+/// Vectors of up to `max_len` values from `element`, shrinking towards
+/// fewer elements and then towards simpler ones.
+pub fn vecs<G: Generator>(element: G, max_len: usize) -> Vecs<G> {
+    Vecs { element, max_len }
+}
+
+#[derive(Debug, Clone)]
+pub struct Vecs<G> {
+    element: G,
+    max_len: usize,
+}
+
+impl<G: Generator> Generator for Vecs<G> {
+    type Value = Vec<G::Value>;
+
+    fn generate(&self, rng: &mut Rng) -> Vec<G::Value> {
+        let len = rng.in_range(&(0..=self.max_len as i64));
+        (0..len).map(|_| self.element.generate(rng)).collect()
+    }
+
+    fn shrink(&self, value: &Vec<G::Value>) -> Vec<Vec<G::Value>> {
+        let mut candidates = shorter(value);
+
+        for (i, item) in value.iter().enumerate() {
+            for simpler in self.element.shrink(item) {
+                let mut candidate = value.clone();
+                candidate[i] = simpler;
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+/// `items` with chunks taken out: the first and second halves, then
+/// quarters and so on down to single items.
+fn shorter<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    let mut candidates = Vec::new();
+    let mut chunk = items.len();
+
+    while chunk > 0 {
+        for start in (0..items.len()).step_by(chunk) {
+            let end = (start + chunk).min(items.len());
+            candidates.push([&items[..start], &items[end..]].concat());
+        }
+        chunk /= 2;
+    }
+    candidates
+}
+
+/// How many cases to try, and the seed that picks them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    pub cases: u32,
+    pub seed: u64,
+    /// Give up shrinking after this many steps and report what we have.
+    pub max_shrinks: u32,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            cases: 256,
+            seed: 0x5eed,
+            max_shrinks: 10_000,
+        }
+    }
+}
+
+/// A value that broke a property.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure<T> {
+    /// The random value that failed first.
+    pub original: T,
+    /// The simplest failing value shrinking could find.
+    pub minimal: T,
+    /// Which case failed, counting from zero.
+    pub case: u32,
+    pub shrinks: u32,
+}
+
+impl Config {
+    /// Runs `property` on `self.cases` values from `generator` and returns
+    /// the first failure, shrunk, if there is one.
+    pub fn run<G, P>(&self, generator: &G, property: P) -> Option<Failure<G::Value>>
+    where
+        G: Generator,
+        P: Fn(&G::Value) -> bool,
+    {
+        let mut rng = Rng::new(self.seed);
+
+        for case in 0..self.cases {
+            let value = generator.generate(&mut rng);
+            if !property(&value) {
+                let (minimal, shrinks) = self.shrink(generator, &property, value.clone());
+                return Some(Failure {
+                    original: value,
+                    minimal,
+                    case,
+                    shrinks,
+                });
+            }
+        }
+        None
+    }
+
+    fn shrink<G, P>(&self, generator: &G, property: &P, mut value: G::Value) -> (G::Value, u32)
+    where
+        G: Generator,
+        P: Fn(&G::Value) -> bool,
+    {
+        let mut shrinks = 0;
+
+        'search: while shrinks < self.max_shrinks {
+            for candidate in generator.shrink(&value) {
+                if !property(&candidate) {
+                    value = candidate;
+                    shrinks += 1;
+                    continue 'search;
+                }
+            }
+            break;
+        }
+        (value, shrinks)
+    }
+
+    /// Like [`check`], with these settings.
+    pub fn check<G, P>(&self, generator: G, property: P)
+    where
+        G: Generator,
+        P: Fn(&G::Value) -> bool,
+    {
+        if let Some(failure) = self.run(&generator, property) {
+            panic!(
+                "property failed for {:?} (case {} with seed {:#x}; \
+                 shrunk from {:?} in {} steps)",
+                failure.minimal, failure.case, self.seed, failure.original, failure.shrinks
+            );
+        }
+    }
+}
+
+/// Checks that `property` holds for many values from `generator`, and
+/// panics with a minimal counterexample if it does not.
+pub fn check<G, P>(generator: G, property: P)
+where
+    G: Generator,
+    P: Fn(&G::Value) -> bool,
+{
+    Config::default().check(generator, property)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure<G, P>(generator: G, property: P) -> Failure<G::Value>
+    where
+        G: Generator,
+        P: Fn(&G::Value) -> bool,
+    {
+        Config::default()
+            .run(&generator, property)
+            .expect("property should fail")
+    }
+
+    #[test]
+    fn same_seed_same_values() {
+        let generator = vecs(ints(-50..=50), 20);
+        let values = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..10)
+                .map(|_| generator.generate(&mut rng))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(values(1), values(1));
+        assert_ne!(values(1), values(2));
+    }
+
+    #[test]
+    fn ints_stay_in_range_and_reach_both_ends() {
+        let mut rng = Rng::new(3);
+        let values: Vec<i64> = (0..500).map(|_| ints(-5..=5).generate(&mut rng)).collect();
+
+        assert!(values.iter().all(|n| (-5..=5).contains(n)));
+        assert!(values.contains(&-5) && values.contains(&5));
+        // The widest range must not overflow.
+        rng.in_range(&(i64::MIN..=i64::MAX));
+    }
+
+    #[test]
+    fn shrinks_ints_to_the_boundary() {
+        assert_eq!(failure(ints(0..=1000), |&n| n < 500).minimal, 500);
+        assert_eq!(failure(ints(-1000..=1000), |&n| n > -321).minimal, -321);
+        assert_eq!(failure(ints(10..=20), |_| false).minimal, 10);
+        assert_eq!(
+            failure(ints(i64::MIN..=i64::MAX), |&n| n < i64::MAX / 3).minimal,
+            i64::MAX / 3
+        );
+    }
+
+    #[test]
+    fn shrinks_strings_to_the_offending_character() {
+        let found = failure(strings(30), |s| !s.contains('~'));
+
+        assert_eq!(found.minimal, "~");
+        assert!(found.original.contains('~'));
+    }
+
+    #[test]
+    fn shrinks_vecs_by_length_then_elements() {
+        let found = failure(vecs(ints(0..=100), 10), |v| v.iter().all(|&n| n < 50));
+        assert_eq!(found.minimal, [50]);
+
+        let found = failure(vecs(ints(0..=100), 10), |v| v.len() < 3);
+        assert_eq!(found.minimal, [0, 0, 0]);
+    }
+
+    #[test]
+    fn passing_properties_have_no_failure() {
+        assert_eq!(Config::default().run(&strings(10), |s| s.len() <= 10), None);
+        check(vecs(ints(-9..=9), 5), |v| v.iter().all(|n| n.abs() <= 9));
+    }
+
+    #[test]
+    #[should_panic(expected = "property failed for 13")]
+    fn check_panics_with_the_minimal_value() {
+        check(ints(0..=100), |&n| n < 13);
+    }
+}
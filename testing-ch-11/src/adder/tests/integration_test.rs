@@ -0,0 +1,35 @@
+use adder::{add_two, greeting, Guess, Rectangle};
+
+#[test]
+fn it_adds_two() {
+    assert_eq!(4, add_two(2));
+}
+
+#[test]
+fn rectangles_hold_strictly_smaller_ones() {
+    let larger = Rectangle {
+        width: 8,
+        height: 7,
+    };
+    let smaller = Rectangle {
+        width: 5,
+        height: 1,
+    };
+
+    assert!(larger.can_hold(&smaller));
+    assert!(!smaller.can_hold(&larger));
+    assert!(!larger.can_hold(&larger));
+}
+
+#[test]
+fn greeting_contains_name() {
+    assert_eq!(greeting("Carol"), "Hello Carol!");
+}
+
+#[test]
+fn guesses_parse_from_user_input() {
+    let guess: Guess = "42\n".parse().unwrap();
+
+    assert_eq!(guess.value(), 42);
+    assert!("0".parse::<Guess>().is_err());
+}